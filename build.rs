@@ -1,37 +1,33 @@
-use std::collections::HashMap;
+use blog_content::site::og_card_path;
+use blog_content::{
+    check_links, generate_feeds, generate_robots, generate_sitemap, highlight_css, og_card_svg,
+    scan_blogs_directory, OgCardRenderer, PostIndex, SearchIndex, SiteConfig,
+};
+use std::env;
+use std::fmt::Write as _;
 use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
-
-/// 简化的文章元数据结构（用于构建时）
-#[derive(serde::Serialize, Clone)]
-struct PostMetadataJson {
-    title: String,
-    date: Option<String>,
-    author: Option<String>,
-    tags: Option<Vec<String>>,
-    categories: Option<Vec<String>>,
-    summary: Option<String>,
-    cover_image: Option<String>,
-    slug: String,
-    draft: bool,
-    updated: Option<String>,
-    layout: Option<String>,
-    file_path: String,
-}
-
-/// 文章索引 JSON 结构
-#[derive(serde::Serialize)]
-struct PostIndexJson {
-    posts: Vec<PostMetadataJson>,
-    tags: HashMap<String, Vec<usize>>,
-    categories: HashMap<String, Vec<usize>>,
-    sorted_by_date: Vec<usize>,
-}
+use std::path::{Path, PathBuf};
 
 fn main() {
     println!("cargo:rerun-if-changed=blogs");
+    println!("cargo:rerun-if-changed=site.yaml");
+
+    // 读取站点配置并写入 OUT_DIR，供运行时使用
+    let site = load_site_config();
+    if let Err(e) = write_site_json(&site) {
+        println!("cargo:warning=无法写入站点配置: {}", e);
+    }
     
+    // 生成代码高亮样式（亮色 / 暗色主题）
+    match highlight_css() {
+        Ok(css) => {
+            if let Err(e) = write_public_files([("highlight.css".to_string(), css)]) {
+                println!("cargo:warning=无法写入代码高亮样式: {}", e);
+            }
+        }
+        Err(e) => println!("cargo:warning=生成代码高亮样式失败: {}", e),
+    }
+
     // 检查 blogs 目录是否存在
     let blogs_dir = Path::new("blogs");
     if !blogs_dir.exists() {
@@ -41,256 +37,106 @@ fn main() {
         return;
     }
     
-    // 扫描并处理 Markdown 文件
-    match scan_and_process_blogs(blogs_dir) {
-        Ok(index) => {
+    // 扫描、解析并渲染 Markdown 文件（与运行时共用 blog-content 的解析规则）
+    match scan_blogs_directory(blogs_dir) {
+        Ok(mut index) => {
+            // 渲染时发现的问题（如未定义的公式标签）
+            for post in &index.posts {
+                for warning in &post.warnings {
+                    println!("cargo:warning={}: {}", post.file_path, warning);
+                }
+            }
+
+            // 页面包中的图片等资源复制到 public/assets/posts/<slug>/
+            if let Err(e) = write_bundle_assets(blogs_dir, &index) {
+                println!("cargo:warning=无法复制页面包资源: {}", e);
+            }
+
+            // 为没有封面的文章生成社交卡片，并作为默认封面写入索引
+            if let Err(e) = write_og_cards(&mut index, &site) {
+                println!("cargo:warning=无法写入社交卡片: {}", e);
+            }
+
             // 生成 JSON 索引文件
             if let Err(e) = write_index_json(&index) {
-                eprintln!("cargo:warning=无法写入索引文件: {}", e);
+                println!("cargo:warning=无法写入索引文件: {}", e);
             } else {
                 println!("cargo:warning=成功生成文章索引，共 {} 篇文章", index.posts.len());
             }
-        }
-        Err(e) => {
-            eprintln!("cargo:warning=处理 blogs 目录时出错: {}", e);
-            create_empty_index();
-        }
-    }
-}
 
-/// 扫描并处理 blogs 目录
-fn scan_and_process_blogs(blogs_dir: &Path) -> Result<PostIndexJson, Box<dyn std::error::Error>> {
-    let mut index = PostIndexJson {
-        posts: Vec::new(),
-        tags: HashMap::new(),
-        categories: HashMap::new(),
-        sorted_by_date: Vec::new(),
-    };
-    
-    let mut posts_with_dates: Vec<(usize, Option<chrono::NaiveDate>)> = Vec::new();
-    
-    // 遍历 blogs 目录
-    for entry in WalkDir::new(blogs_dir)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .and_then(|s| s.to_str())
-                .map(|ext| ext == "md")
-                .unwrap_or(false)
-        })
-    {
-        let file_path = entry.path();
-        
-        // 处理 Markdown 文件
-        match parse_markdown_frontmatter(file_path) {
-            Ok(Some(metadata)) => {
-                // 跳过草稿
-                if metadata.draft {
-                    continue;
-                }
-                
-                let post_idx = index.posts.len();
-                index.posts.push(metadata.clone());
-                
-                // 构建标签索引
-                if let Some(ref tags) = metadata.tags {
-                    for tag in tags {
-                        index
-                            .tags
-                            .entry(tag.clone())
-                            .or_insert_with(Vec::new)
-                            .push(post_idx);
-                    }
-                }
-                
-                // 构建分类索引
-                if let Some(ref categories) = metadata.categories {
-                    for category in categories {
-                        index
-                            .categories
-                            .entry(category.clone())
-                            .or_insert_with(Vec::new)
-                            .push(post_idx);
+            // 生成预渲染的文章正文
+            if let Err(e) = write_post_bodies(&index) {
+                println!("cargo:warning=无法写入文章正文: {}", e);
+            }
+
+            // 生成全文搜索索引
+            if let Err(e) = write_search_index(&SearchIndex::build(&index)) {
+                println!("cargo:warning=无法写入搜索索引: {}", e);
+            }
+
+            // 生成 RSS / Atom / JSON Feed
+            match generate_feeds(&index, &site) {
+                Ok(feeds) => {
+                    let files = feeds.into_iter().map(|feed| (feed.path, feed.content));
+                    if let Err(e) = write_public_files(files) {
+                        println!("cargo:warning=无法写入订阅源: {}", e);
                     }
                 }
-                
-                // 记录日期用于排序
-                let date = metadata.date.as_ref().and_then(|d| {
-                    chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()
-                });
-                posts_with_dates.push((post_idx, date));
+                Err(e) => println!("cargo:warning=生成订阅源失败: {}", e),
+            }
+
+            // 生成 sitemap.xml 和 robots.txt
+            let files = [
+                ("sitemap.xml".to_string(), generate_sitemap(&index, &site)),
+                ("robots.txt".to_string(), generate_robots(&site)),
+            ];
+            if let Err(e) = write_public_files(files) {
+                println!("cargo:warning=无法写入站点地图: {}", e);
             }
-            Ok(None) => {
-                // 没有 frontmatter，跳过
-                continue;
+
+            // 检查站内链接与锚点（放在最后，生成的订阅源、社交卡片等文件都已写入 public/）
+            let broken = check_links(&index, &site, Path::new("."));
+            for link in &broken {
+                println!("cargo:warning={}", link);
             }
-            Err(e) => {
-                eprintln!(
-                    "cargo:warning=处理文件失败 {}: {}",
-                    file_path.display(),
-                    e
-                );
-                // 继续处理其他文件
+            if site.strict_links && !broken.is_empty() {
+                panic!("发现 {} 个失效链接（site.yaml 中 strict_links 为 true）", broken.len());
             }
         }
-    }
-    
-    // 按日期排序（从新到旧）
-    posts_with_dates.sort_by(|a, b| {
-        match (a.1, b.1) {
-            (Some(date_a), Some(date_b)) => date_b.cmp(&date_a),
-            (Some(_), None) => std::cmp::Ordering::Less,
-            (None, Some(_)) => std::cmp::Ordering::Greater,
-            (None, None) => std::cmp::Ordering::Equal,
+        Err(e) => {
+            // 例如 slug 重复：直接让构建失败，避免运行时路由到错误的文章
+            panic!("处理 blogs 目录时出错: {:#}", e);
         }
-    });
-    index.sorted_by_date = posts_with_dates.into_iter().map(|(idx, _)| idx).collect();
-    
-    Ok(index)
+    }
 }
 
-/// 解析 Markdown 文件的 Frontmatter
-fn parse_markdown_frontmatter(
-    path: &Path,
-) -> Result<Option<PostMetadataJson>, Box<dyn std::error::Error>> {
-    let content = fs::read_to_string(path)?;
-    
-    // 检查是否以 --- 开头
-    if !content.starts_with("---") {
-        return Ok(None);
+/// 读取 `site.yaml`，文件不存在或解析失败时使用默认配置
+fn load_site_config() -> SiteConfig {
+    let path = Path::new("site.yaml");
+    if !path.exists() {
+        return SiteConfig::default();
     }
-    
-    // 查找第二个 ---
-    let end_marker = content[3..]
-        .find("---")
-        .ok_or("Frontmatter 格式错误：未找到结束标记")?;
-    
-    // 提取 Frontmatter YAML
-    let frontmatter_str = &content[3..end_marker + 3];
-    
-    // 解析 YAML
-    let metadata: serde_yaml::Value = serde_yaml::from_str(frontmatter_str)?;
-    
-    // 提取字段
-    let title = metadata
-        .get("title")
-        .and_then(|v| v.as_str())
-        .unwrap_or("未命名文章")
-        .to_string();
-    
-    let date = metadata
-        .get("date")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-    
-    let author = metadata
-        .get("author")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-    
-    let tags = metadata
-        .get("tags")
-        .and_then(|v| v.as_sequence())
-        .map(|seq| {
-            seq.iter()
-                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                .collect()
-        });
-    
-    let categories = metadata
-        .get("categories")
-        .and_then(|v| v.as_sequence())
-        .map(|seq| {
-            seq.iter()
-                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                .collect()
-        });
-    
-    let summary = metadata
-        .get("summary")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-    
-    let cover_image = metadata
-        .get("cover_image")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-    
-    let slug = metadata
-        .get("slug")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-        .unwrap_or_else(|| {
-            // 从文件名生成 slug
-            let stem = path
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("untitled");
-            
-            // 移除日期前缀（如果存在）
-            let slug = if stem.len() > 11 && stem.chars().take(4).all(|c| c.is_ascii_digit()) {
-                if let Some(dash_pos) = stem[4..].find('-') {
-                    if dash_pos == 0 && stem.len() > 10 && &stem[7..8] == "-" {
-                        &stem[11..]
-                    } else {
-                        stem
-                    }
-                } else {
-                    stem
-                }
-            } else {
-                stem
-            };
-            
-            slug.to_lowercase()
-                .replace(' ', "-")
-                .replace('_', "-")
-                .chars()
-                .filter(|c| c.is_alphanumeric() || *c == '-')
-                .collect()
-        });
-    
-    let draft = metadata
-        .get("draft")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
-    
-    let updated = metadata
-        .get("updated")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-    
-    let layout = metadata
-        .get("layout")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-    
-    let file_path = path
-        .strip_prefix("blogs")
-        .unwrap_or(path)
-        .to_string_lossy()
-        .replace('\\', "/");
-    
-    Ok(Some(PostMetadataJson {
-        title,
-        date,
-        author,
-        tags,
-        categories,
-        summary,
-        cover_image,
-        slug,
-        draft,
-        updated,
-        layout,
-        file_path,
-    }))
+
+    match fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|yaml| {
+        SiteConfig::from_yaml(&yaml).map_err(|e| e.to_string())
+    }) {
+        Ok(site) => site,
+        Err(e) => {
+            println!("cargo:warning=site.yaml 解析失败，使用默认配置: {}", e);
+            SiteConfig::default()
+        }
+    }
+}
+
+/// 写入 `OUT_DIR/site.json`
+fn write_site_json(site: &SiteConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    fs::write(out_dir.join("site.json"), site.to_json()?)?;
+    Ok(())
 }
 
 /// 写入索引 JSON 文件
-fn write_index_json(index: &PostIndexJson) -> Result<(), Box<dyn std::error::Error>> {
+fn write_index_json(index: &PostIndex) -> Result<(), Box<dyn std::error::Error>> {
     // 确保 assets 目录存在
     let assets_dir = Path::new("assets");
     if !assets_dir.exists() {
@@ -299,7 +145,7 @@ fn write_index_json(index: &PostIndexJson) -> Result<(), Box<dyn std::error::Err
     
     // 写入 JSON 文件
     let json_path = assets_dir.join("posts_index.json");
-    let json_content = serde_json::to_string_pretty(index)?;
+    let json_content = index.to_json()?;
     fs::write(&json_path, json_content)?;
     
     println!("cargo:warning=索引文件已生成: {}", json_path.display());
@@ -307,16 +153,107 @@ fn write_index_json(index: &PostIndexJson) -> Result<(), Box<dyn std::error::Err
     Ok(())
 }
 
+/// 将生成的静态文件写入 `public/`，dx 打包时会原样复制到站点根目录
+fn write_public_files<C: AsRef<[u8]>>(
+    files: impl IntoIterator<Item = (String, C)>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let public_dir = Path::new("public");
+    for (relative_path, content) in files {
+        let path = public_dir.join(relative_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, content)?;
+    }
+    Ok(())
+}
+
+/// 为没有 `cover_image` 的文章渲染 PNG 社交卡片到 `public/assets/og/`，
+/// 并把卡片地址设为该文章的封面；手动指定了封面的文章保持不变
+fn write_og_cards(index: &mut PostIndex, site: &SiteConfig) -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=assets/fonts");
+
+    // 除系统字体外，还可以把字体文件放在 assets/fonts/ 下，保证各环境渲染一致
+    let fonts_dir = Path::new("assets/fonts");
+    let renderer = OgCardRenderer::new(&[fonts_dir]);
+    let mut cards = Vec::new();
+    for post in index.posts.iter_mut() {
+        if post.metadata.cover_image.is_some() {
+            continue;
+        }
+        let slug = post.slug();
+        match renderer.render_png(&og_card_svg(post, site)) {
+            Ok(png) => {
+                post.metadata.cover_image = Some(og_card_path(&slug));
+                cards.push((format!("assets/og/{}.png", slug), png));
+            }
+            Err(e) => println!("cargo:warning=文章 {} 的社交卡片渲染失败: {:#}", slug, e),
+        }
+    }
+    write_public_files(cards)
+}
+
+/// 把页面包（`blogs/<目录>/index.md`）同目录的资源文件复制到 `public/assets/posts/<slug>/`，
+/// 与渲染时改写后的图片地址对应
+fn write_bundle_assets(blogs_dir: &Path, index: &PostIndex) -> Result<(), Box<dyn std::error::Error>> {
+    let mut files = Vec::new();
+    for post in &index.posts {
+        let bundle_dir = blogs_dir.join(&post.file_path);
+        let bundle_dir = bundle_dir.parent().unwrap_or(blogs_dir);
+        for asset in &post.assets {
+            let content = fs::read(bundle_dir.join(asset))?;
+            files.push((format!("assets/posts/{}/{}", post.slug(), asset), content));
+        }
+    }
+    write_public_files(files)
+}
+
+/// 将每篇文章预渲染的 HTML 写入 OUT_DIR，并生成 `post_bodies.rs`
+///
+/// `post_bodies.rs` 通过 `include_str!` 嵌入这些 HTML，运行时按 slug 查找，
+/// 因此 WASM 包中不再需要 Markdown 解析器
+fn write_post_bodies(index: &PostIndex) -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    let posts_dir = out_dir.join("posts");
+    fs::create_dir_all(&posts_dir)?;
+
+    let mut source = String::from("/// build.rs 生成：slug 到预渲染 HTML 正文的映射\n");
+    source.push_str("pub static POST_BODIES: &[(&str, &str)] = &[\n");
+    for (idx, post) in index.posts.iter().enumerate() {
+        let html_path = posts_dir.join(format!("post-{}.html", idx));
+        fs::write(&html_path, &post.html_content)?;
+
+        let slug = post.metadata.slug.clone().unwrap_or_default();
+        writeln!(
+            source,
+            "    ({:?}, include_str!({:?})),",
+            slug,
+            html_path.to_string_lossy()
+        )?;
+    }
+    source.push_str("];\n");
+
+    fs::write(out_dir.join("post_bodies.rs"), source)?;
+    Ok(())
+}
+
+/// 写入 `OUT_DIR/search_index.json`，运行时嵌入 WASM 包在本地检索
+fn write_search_index(search: &SearchIndex) -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    fs::write(out_dir.join("search_index.json"), search.to_json()?)?;
+    Ok(())
+}
+
 /// 创建空的索引文件
 fn create_empty_index() {
-    let index = PostIndexJson {
-        posts: Vec::new(),
-        tags: HashMap::new(),
-        categories: HashMap::new(),
-        sorted_by_date: Vec::new(),
-    };
-    
+    let index = PostIndex::new();
     if let Err(e) = write_index_json(&index) {
-        eprintln!("cargo:warning=无法创建空索引文件: {}", e);
+        println!("cargo:warning=无法创建空索引文件: {}", e);
+    }
+    if let Err(e) = write_post_bodies(&index) {
+        println!("cargo:warning=无法创建空文章正文: {}", e);
+    }
+    if let Err(e) = write_search_index(&SearchIndex::default()) {
+        println!("cargo:warning=无法创建空搜索索引: {}", e);
     }
 }
@@ -0,0 +1,67 @@
+//! 博客内容命令行工具
+//!
+//! ```text
+//! cargo run -p blog-content --bin blog -- lint [blogs 目录]
+//! ```
+//!
+//! 在仓库根目录运行：读取 `site.yaml`，检查目录下的所有文章，有错误时以非零状态退出。
+
+use blog_content::{lint_directory, SiteConfig};
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "用法: blog lint [blogs 目录]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["lint"] => lint(Path::new("blogs")),
+        ["lint", dir] => lint(Path::new(dir)),
+        _ => {
+            eprintln!("{}", USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn lint(blogs_dir: &Path) -> ExitCode {
+    let site = match load_site_config(Path::new("site.yaml")) {
+        Ok(site) => site,
+        Err(e) => {
+            eprintln!("site.yaml: 错误: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if !blogs_dir.is_dir() {
+        eprintln!("{}: 错误: 目录不存在", blogs_dir.display());
+        return ExitCode::FAILURE;
+    }
+
+    let report = lint_directory(blogs_dir, Path::new("."), &site);
+    for diagnostic in &report.diagnostics {
+        println!("{}", diagnostic);
+    }
+    println!(
+        "检查了 {} 篇文章：{} 个错误，{} 个警告",
+        report.files,
+        report.errors(),
+        report.warnings()
+    );
+
+    if report.errors() > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// 读取站点配置，文件不存在时使用默认配置
+fn load_site_config(path: &Path) -> Result<SiteConfig, String> {
+    if !path.exists() {
+        return Ok(SiteConfig::default());
+    }
+    let yaml = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    SiteConfig::from_yaml(&yaml).map_err(|e| e.to_string())
+}
@@ -0,0 +1,399 @@
+use crate::feed::escape_xml;
+use crate::{slugify, CitationStyle};
+use anyhow::{anyhow, Context, Result};
+use biblatex::{ChunksExt, Entry};
+use std::fs;
+use std::path::Path;
+
+/// 一条参考文献
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    /// BibTeX 条目的键
+    pub key: String,
+    /// 作者（姓，名）
+    pub authors: Vec<(String, String)>,
+    /// 年份
+    pub year: Option<String>,
+    pub title: String,
+    /// 期刊或论文集名称
+    pub container: Option<String>,
+    pub publisher: Option<String>,
+    pub doi: Option<String>,
+    pub url: Option<String>,
+}
+
+impl Reference {
+    fn from_entry(entry: &Entry) -> Self {
+        let field = |name: &str| {
+            entry
+                .get(name)
+                .map(|chunks| chunks.format_verbatim().trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let authors = entry
+            .author()
+            .or_else(|_| entry.editors().map(|editors| {
+                editors.into_iter().flat_map(|(persons, _)| persons).collect()
+            }))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|person| {
+                let family = [person.prefix.as_str(), person.name.as_str()]
+                    .iter()
+                    .filter(|part| !part.is_empty())
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                (family, person.given_name)
+            })
+            .collect();
+        let year = field("year").or_else(|| {
+            field("date").and_then(|date| date.get(..4).map(str::to_string))
+        });
+
+        Self {
+            key: entry.key.clone(),
+            authors,
+            year,
+            title: field("title").unwrap_or_else(|| entry.key.clone()),
+            container: field("journal")
+                .or_else(|| field("journaltitle"))
+                .or_else(|| field("booktitle")),
+            publisher: field("publisher").or_else(|| field("institution")),
+            doi: field("doi"),
+            url: field("url"),
+        }
+    }
+
+    /// 锚点 `id`
+    pub fn anchor(&self) -> String {
+        format!("ref-{}", slugify(&self.key))
+    }
+
+    /// 作者年份格式中的作者部分：一位写姓，两位用 `&` 连接，三位及以上写 `et al.`
+    fn short_authors(&self) -> String {
+        match self.authors.as_slice() {
+            [] => self.title.clone(),
+            [(first, _)] => first.clone(),
+            [(first, _), (second, _)] => format!("{} & {}", first, second),
+            [(first, _), ..] => format!("{} et al.", first),
+        }
+    }
+
+    fn year_or_nd(&self) -> &str {
+        self.year.as_deref().unwrap_or("n.d.")
+    }
+
+    /// 参考文献列表中的一条（HTML）
+    fn to_html(&self) -> String {
+        let names: Vec<String> = self
+            .authors
+            .iter()
+            .map(|(family, given)| {
+                let initials: Vec<String> = given
+                    .split([' ', '-'])
+                    .filter_map(|part| part.chars().next())
+                    .map(|c| format!("{}.", c))
+                    .collect();
+                if initials.is_empty() {
+                    family.clone()
+                } else {
+                    format!("{}, {}", family, initials.join(" "))
+                }
+            })
+            .collect();
+        let authors = match names.as_slice() {
+            [] => String::new(),
+            [only] => only.clone(),
+            [init @ .., last] => format!("{}, & {}", init.join(", "), last),
+        };
+
+        let mut html = String::new();
+        if !authors.is_empty() {
+            html.push_str(&format!("{} ", escape_xml(&authors)));
+        }
+        html.push_str(&format!("({}). ", escape_xml(self.year_or_nd())));
+        match &self.container {
+            Some(container) => html.push_str(&format!(
+                "{}. <em>{}</em>.",
+                escape_xml(&self.title),
+                escape_xml(container)
+            )),
+            None => html.push_str(&format!("<em>{}</em>.", escape_xml(&self.title))),
+        }
+        if let Some(publisher) = &self.publisher {
+            html.push_str(&format!(" {}.", escape_xml(publisher)));
+        }
+        let link = match (&self.doi, &self.url) {
+            (Some(doi), _) => Some(format!("https://doi.org/{}", doi)),
+            (None, Some(url)) => Some(url.clone()),
+            (None, None) => None,
+        };
+        if let Some(link) = link {
+            html.push_str(&format!(
+                " <a href=\"{}\">{}</a>",
+                escape_xml(&link),
+                escape_xml(&link)
+            ));
+        }
+        html
+    }
+}
+
+/// BibTeX 参考文献库
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bibliography {
+    references: Vec<Reference>,
+}
+
+impl Bibliography {
+    /// 解析 BibTeX 文本
+    pub fn parse(src: &str) -> Result<Self> {
+        let bibliography =
+            biblatex::Bibliography::parse(src).map_err(|e| anyhow!("BibTeX 解析失败: {}", e))?;
+        Ok(Self {
+            references: bibliography.iter().map(Reference::from_entry).collect(),
+        })
+    }
+
+    /// 读取并解析 `.bib` 文件
+    pub fn load(path: &Path) -> Result<Self> {
+        let src = fs::read_to_string(path)
+            .with_context(|| format!("无法读取参考文献文件: {}", path.display()))?;
+        Self::parse(&src).with_context(|| format!("参考文献文件格式错误: {}", path.display()))
+    }
+
+    /// 按键查找条目
+    pub fn get(&self, key: &str) -> Option<&Reference> {
+        self.references.iter().find(|reference| reference.key == key)
+    }
+}
+
+/// 行内引用中的一项：`@key` 及可选的页码等定位信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Cite {
+    key: String,
+    locator: Option<String>,
+}
+
+/// 解析 `[@key]`、`[@a; @b]`、`[@key, p. 12]` 中括号内的部分，不是引用时返回 `None`
+fn parse_cites(inner: &str) -> Option<Vec<Cite>> {
+    inner
+        .split(';')
+        .map(|part| {
+            let part = part.trim().strip_prefix('@')?;
+            let (key, locator) = match part.split_once(',') {
+                Some((key, locator)) => (key.trim_end(), Some(locator.trim().to_string())),
+                None => (part.trim_end(), None),
+            };
+            let valid = !key.is_empty()
+                && key
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | ':' | '.' | '/'));
+            valid.then(|| Cite {
+                key: key.to_string(),
+                locator: locator.filter(|locator| !locator.is_empty()),
+            })
+        })
+        .collect()
+}
+
+/// 一篇文章中的文献引用：按首次出现的顺序编号，生成行内引用和文末的参考文献列表
+#[derive(Debug, Clone)]
+pub struct Citations<'a> {
+    bibliography: &'a Bibliography,
+    style: CitationStyle,
+    cited: Vec<&'a Reference>,
+    /// 未知的文献键等问题
+    pub warnings: Vec<String>,
+}
+
+impl<'a> Citations<'a> {
+    pub fn new(bibliography: &'a Bibliography, style: CitationStyle) -> Self {
+        Self {
+            bibliography,
+            style,
+            cited: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// 把文本中的 `[@key]` 替换为行内引用 HTML，其余文本交给 `plain` 转换为 HTML
+    pub fn render_text(&mut self, text: &str, mut plain: impl FnMut(&str) -> String) -> String {
+        let mut html = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find("[@") {
+            let cites = rest[start + 1..]
+                .find(']')
+                .and_then(|len| Some((len, parse_cites(&rest[start + 1..start + 1 + len])?)));
+            let Some((len, cites)) = cites else {
+                html.push_str(&plain(&rest[..start + 2]));
+                rest = &rest[start + 2..];
+                continue;
+            };
+            html.push_str(&plain(&rest[..start]));
+            html.push_str(&self.cite(&cites, &rest[start..start + len + 2]));
+            rest = &rest[start + len + 2..];
+        }
+        html.push_str(&plain(rest));
+        html
+    }
+
+    /// 渲染一处行内引用
+    fn cite(&mut self, cites: &[Cite], source: &str) -> String {
+        let mut parts = Vec::new();
+        for cite in cites {
+            let Some(reference) = self.bibliography.get(&cite.key) else {
+                let warning = format!("未知的文献条目: {}", cite.key);
+                if !self.warnings.contains(&warning) {
+                    self.warnings.push(warning);
+                }
+                return format!(
+                    "<span class=\"citation citation-missing\">{}</span>",
+                    escape_xml(source)
+                );
+            };
+            let number = match self.cited.iter().position(|cited| cited.key == reference.key) {
+                Some(index) => index + 1,
+                None => {
+                    self.cited.push(reference);
+                    self.cited.len()
+                }
+            };
+            let label = match self.style {
+                CitationStyle::Numeric => number.to_string(),
+                CitationStyle::AuthorYear => {
+                    format!("{}, {}", reference.short_authors(), reference.year_or_nd())
+                }
+            };
+            let mut part = format!(
+                "<a href=\"#{}\">{}</a>",
+                reference.anchor(),
+                escape_xml(&label)
+            );
+            if let Some(locator) = &cite.locator {
+                part.push_str(&format!(", {}", escape_xml(locator)));
+            }
+            parts.push(part);
+        }
+
+        match self.style {
+            CitationStyle::Numeric => {
+                format!("<span class=\"citation\">[{}]</span>", parts.join(", "))
+            }
+            CitationStyle::AuthorYear => {
+                format!("<span class=\"citation\">({})</span>", parts.join("; "))
+            }
+        }
+    }
+
+    /// 文末的参考文献列表；没有引用时返回 `None`
+    ///
+    /// 编号格式按引用顺序列出，作者年份格式按作者和年份排序
+    pub fn references_html(&self) -> Option<String> {
+        if self.cited.is_empty() {
+            return None;
+        }
+        let mut references = self.cited.clone();
+        let tag = match self.style {
+            CitationStyle::Numeric => "ol",
+            CitationStyle::AuthorYear => {
+                references.sort_by_key(|reference| {
+                    (reference.short_authors(), reference.year_or_nd().to_string())
+                });
+                "ul"
+            }
+        };
+
+        let mut html = format!("<{} class=\"references-list\">\n", tag);
+        for reference in references {
+            html.push_str(&format!(
+                "<li id=\"{}\">{}</li>\n",
+                reference.anchor(),
+                reference.to_html()
+            ));
+        }
+        html.push_str(&format!("</{}>\n", tag));
+        Some(html)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BIB: &str = r#"
+@article{knuth84,
+  author = {Donald E. Knuth},
+  title = {Literate Programming},
+  journal = {The Computer Journal},
+  year = 1984,
+  doi = {10.1093/comjnl/27.2.97}
+}
+@book{lamport94,
+  author = {Lamport, Leslie and Goossens, Michel and Mittelbach, Frank},
+  title = {LaTeX: A Document Preparation System},
+  publisher = {Addison-Wesley},
+  date = {1994-01}
+}
+"#;
+
+    #[test]
+    fn test_parse_bibliography() {
+        let bibliography = Bibliography::parse(BIB).unwrap();
+        let knuth = bibliography.get("knuth84").unwrap();
+        assert_eq!(knuth.authors, vec![("Knuth".to_string(), "Donald E.".to_string())]);
+        assert_eq!(knuth.year.as_deref(), Some("1984"));
+        assert_eq!(knuth.container.as_deref(), Some("The Computer Journal"));
+        assert_eq!(bibliography.get("lamport94").unwrap().year.as_deref(), Some("1994"));
+        assert!(Bibliography::parse("@article{broken, title = {x}").is_err());
+    }
+
+    #[test]
+    fn test_parse_cites() {
+        assert_eq!(
+            parse_cites("@a; @b, p. 12"),
+            Some(vec![
+                Cite { key: "a".to_string(), locator: None },
+                Cite { key: "b".to_string(), locator: Some("p. 12".to_string()) },
+            ])
+        );
+        assert_eq!(parse_cites("not a cite"), None);
+        assert_eq!(parse_cites("@"), None);
+    }
+
+    #[test]
+    fn test_numeric_citations() {
+        let bibliography = Bibliography::parse(BIB).unwrap();
+        let mut citations = Citations::new(&bibliography, CitationStyle::Numeric);
+        let html = citations.render_text(
+            "见 [@lamport94] 与 [@knuth84, p. 3; @lamport94]，[@missing] 和 [@ x] & y",
+            escape_xml,
+        );
+        assert_eq!(
+            html,
+            "见 <span class=\"citation\">[<a href=\"#ref-lamport94\">1</a>]</span> 与 \
+             <span class=\"citation\">[<a href=\"#ref-knuth84\">2</a>, p. 3, <a href=\"#ref-lamport94\">1</a>]</span>，\
+             <span class=\"citation citation-missing\">[@missing]</span> 和 [@ x] &amp; y"
+        );
+        assert_eq!(citations.warnings, vec!["未知的文献条目: missing"]);
+
+        let list = citations.references_html().unwrap();
+        assert!(list.starts_with("<ol class=\"references-list\">\n<li id=\"ref-lamport94\">Lamport, L., Goossens, M., &amp; Mittelbach, F. (1994). <em>LaTeX: A Document Preparation System</em>. Addison-Wesley.</li>"));
+        assert!(list.contains("<li id=\"ref-knuth84\">Knuth, D. E. (1984). Literate Programming. <em>The Computer Journal</em>. <a href=\"https://doi.org/10.1093/comjnl/27.2.97\">"));
+    }
+
+    #[test]
+    fn test_author_year_citations() {
+        let bibliography = Bibliography::parse(BIB).unwrap();
+        let mut citations = Citations::new(&bibliography, CitationStyle::AuthorYear);
+        let html = citations.render_text("[@lamport94; @knuth84]", escape_xml);
+        assert_eq!(
+            html,
+            "<span class=\"citation\">(<a href=\"#ref-lamport94\">Lamport et al., 1994</a>; <a href=\"#ref-knuth84\">Knuth, 1984</a>)</span>"
+        );
+        let list = citations.references_html().unwrap();
+        assert!(list.starts_with("<ul class=\"references-list\">\n<li id=\"ref-knuth84\">"));
+        assert!(Citations::new(&bibliography, CitationStyle::Numeric).references_html().is_none());
+    }
+}
@@ -0,0 +1,502 @@
+use crate::site::{blog_path, encode_path_segment, is_external, post_path, resolve, tag_path};
+use crate::{slugify, Post, PostIndex, SiteConfig};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+
+/// 生成的订阅源文件
+#[derive(Debug, Clone)]
+pub struct FeedFile {
+    /// 相对于输出目录的路径，如 `feed.xml`、`tags/rust/atom.xml`
+    pub path: String,
+    /// 文件内容
+    pub content: String,
+}
+
+/// 一个订阅频道：全站或某个标签
+struct Channel<'a> {
+    /// 频道标题
+    title: String,
+    /// 频道对应的站内页面路径
+    page_path: String,
+    /// 订阅源文件所在目录，空字符串表示根目录
+    dir: String,
+    /// 频道内的文章（从新到旧）
+    posts: Vec<&'a Post>,
+}
+
+impl Channel<'_> {
+    fn feed_path(&self, file_name: &str) -> String {
+        if self.dir.is_empty() {
+            file_name.to_string()
+        } else {
+            format!("{}/{}", self.dir, file_name)
+        }
+    }
+
+    /// 订阅源文件的绝对 URL，路径段按 [`encode_path_segment`] 编码
+    fn feed_url(&self, site: &SiteConfig, file_name: &str) -> String {
+        let path: Vec<String> = self.feed_path(file_name).split('/').map(encode_path_segment).collect();
+        site.absolute_url(&path.join("/"))
+    }
+}
+
+/// 根据文章索引生成全站及每个标签的 RSS 2.0、Atom 和 JSON Feed
+///
+/// 文章需要带有渲染后的 `html_content`，因此应在构建时调用
+pub fn generate_feeds(index: &PostIndex, site: &SiteConfig) -> Result<Vec<FeedFile>> {
+    let mut channels = vec![Channel {
+        title: site.title.clone(),
+        page_path: blog_path(1),
+        dir: String::new(),
+        posts: index.get_recent_posts(site.feed_items),
+    }];
+
+    let mut tags: Vec<&String> = index.tags.keys().collect();
+    tags.sort();
+    // 不同的标签（如 `C++` 和 `C`）可能对应同一个目录：与目录名相同的标签使用该目录，
+    // 其余的加上由标签名计算的后缀，新增标签不会改变已有订阅源的地址
+    let mut shared: HashMap<String, usize> = HashMap::new();
+    for tag in &tags {
+        *shared.entry(tag_feed_dir(tag)).or_default() += 1;
+    }
+    let mut dirs = HashSet::new();
+    for tag in tags {
+        let base = tag_feed_dir(tag);
+        let dir = if shared[&base] == 1 || *tag == base {
+            base
+        } else {
+            format!("{}-{:08x}", base, fnv1a(tag))
+        };
+        if !dirs.insert(dir.clone()) {
+            bail!("标签 {} 的订阅源目录 tags/{} 与其他标签冲突", tag, dir);
+        }
+        channels.push(Channel {
+            title: format!("{} - 标签: {}", site.title, tag),
+            page_path: tag_path(tag, 1),
+            dir: format!("tags/{}", dir),
+            posts: index.get_posts_by_tag(tag).into_iter().take(site.feed_items).collect(),
+        });
+    }
+
+    let mut files = Vec::new();
+    for channel in &channels {
+        files.push(FeedFile {
+            path: channel.feed_path("feed.xml"),
+            content: render_rss(channel, site),
+        });
+        files.push(FeedFile {
+            path: channel.feed_path("atom.xml"),
+            content: render_atom(channel, site),
+        });
+        files.push(FeedFile {
+            path: channel.feed_path("feed.json"),
+            content: render_json_feed(channel, site)?,
+        });
+    }
+
+    Ok(files)
+}
+
+/// 标签订阅源所在的目录名
+pub fn tag_feed_dir(tag: &str) -> String {
+    let slug = slugify(tag);
+    if slug.is_empty() {
+        encode_path_segment(tag)
+    } else {
+        slug
+    }
+}
+
+/// 32 位 FNV-1a 哈希，结果只取决于输入，用于生成稳定的目录后缀
+fn fnv1a(text: &str) -> u32 {
+    text.bytes()
+        .fold(0x811c_9dc5, |hash: u32, byte| (hash ^ byte as u32).wrapping_mul(0x0100_0193))
+}
+
+/// 把文章 HTML 中的相对地址（`href`、`src`）转换为绝对 URL，订阅源阅读器没有页面地址可供解析
+fn absolutize_urls(html: &str, post: &Post, site: &SiteConfig) -> String {
+    let page_path = post_path(&post.slug());
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some((start, attribute)) = [" href=\"", " src=\""]
+        .into_iter()
+        .filter_map(|attribute| rest.find(attribute).map(|start| (start, attribute)))
+        .min()
+    {
+        let value_start = start + attribute.len();
+        let Some(length) = rest[value_start..].find('"') else {
+            break;
+        };
+        let url = &rest[value_start..value_start + length];
+        output.push_str(&rest[..value_start]);
+        if url.is_empty() || is_external(url) {
+            output.push_str(url);
+        } else if url.starts_with(['#', '?']) {
+            output.push_str(&site.absolute_url(&page_path));
+            output.push_str(url);
+        } else {
+            output.push_str(&site.absolute_url(&resolve(&page_path, url)));
+        }
+        rest = &rest[value_start + length..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// 转义 XML 文本和属性值
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 把 HTML 包进 CDATA，内容中的 `]]>` 需要拆开
+fn cdata(html: &str) -> String {
+    format!("<![CDATA[{}]]>", html.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+fn post_url(post: &Post, site: &SiteConfig) -> String {
+    site.absolute_url(&post_path(&post.slug()))
+}
+
+fn post_author<'a>(post: &'a Post, site: &'a SiteConfig) -> &'a str {
+    post.metadata.author.as_deref().unwrap_or(&site.author)
+}
+
+fn midnight_utc(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+/// 发布时间（日期当天 00:00 UTC）
+fn published(post: &Post) -> Option<DateTime<Utc>> {
+    post.metadata.date.map(midnight_utc)
+}
+
+/// 更新时间：优先 `updated`，否则为发布时间
+fn updated(post: &Post) -> Option<DateTime<Utc>> {
+    post.metadata
+        .updated
+        .map(|dt| dt.and_utc())
+        .or_else(|| published(post))
+}
+
+/// 频道最近的更新时间；没有任何日期时使用 Unix 纪元，保证输出稳定
+fn channel_updated(channel: &Channel) -> DateTime<Utc> {
+    channel
+        .posts
+        .iter()
+        .filter_map(|post| updated(post))
+        .max()
+        .unwrap_or_default()
+}
+
+fn render_rss(channel: &Channel, site: &SiteConfig) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" xmlns:content=\"http://purl.org/rss/1.0/modules/content/\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n");
+    xml.push_str("<channel>\n");
+    xml.push_str(&format!("  <title>{}</title>\n", escape_xml(&channel.title)));
+    xml.push_str(&format!("  <link>{}</link>\n", escape_xml(&site.absolute_url(&channel.page_path))));
+    xml.push_str(&format!("  <description>{}</description>\n", escape_xml(&site.description)));
+    xml.push_str(&format!("  <language>{}</language>\n", escape_xml(&site.language)));
+    xml.push_str(&format!(
+        "  <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        escape_xml(&channel.feed_url(site, "feed.xml"))
+    ));
+    xml.push_str(&format!("  <lastBuildDate>{}</lastBuildDate>\n", channel_updated(channel).to_rfc2822()));
+
+    for post in &channel.posts {
+        let url = post_url(post, site);
+        xml.push_str("  <item>\n");
+        xml.push_str(&format!("    <title>{}</title>\n", escape_xml(&post.metadata.title)));
+        xml.push_str(&format!("    <link>{}</link>\n", escape_xml(&url)));
+        xml.push_str(&format!("    <guid isPermaLink=\"true\">{}</guid>\n", escape_xml(&url)));
+        if let Some(date) = published(post) {
+            xml.push_str(&format!("    <pubDate>{}</pubDate>\n", date.to_rfc2822()));
+        }
+        let author = post_author(post, site);
+        if !author.is_empty() {
+            xml.push_str(&format!("    <dc:creator>{}</dc:creator>\n", escape_xml(author)));
+        }
+        for category in post.metadata.categories.iter().flatten() {
+            xml.push_str(&format!("    <category>{}</category>\n", escape_xml(category)));
+        }
+        let excerpt = post.excerpt_text();
+        if !excerpt.is_empty() {
+            xml.push_str(&format!("    <description>{}</description>\n", escape_xml(&excerpt)));
+        }
+        xml.push_str(&format!("    <content:encoded>{}</content:encoded>\n", cdata(&absolutize_urls(&post.html_content, post, site))));
+        xml.push_str("  </item>\n");
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+fn render_atom(channel: &Channel, site: &SiteConfig) -> String {
+    let page_url = site.absolute_url(&channel.page_path);
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<feed xmlns=\"http://www.w3.org/2005/Atom\" xml:lang=\"{}\">\n",
+        escape_xml(&site.language)
+    ));
+    xml.push_str(&format!("  <title>{}</title>\n", escape_xml(&channel.title)));
+    if !site.description.is_empty() {
+        xml.push_str(&format!("  <subtitle>{}</subtitle>\n", escape_xml(&site.description)));
+    }
+    xml.push_str(&format!("  <link href=\"{}\"/>\n", escape_xml(&page_url)));
+    xml.push_str(&format!(
+        "  <link rel=\"self\" type=\"application/atom+xml\" href=\"{}\"/>\n",
+        escape_xml(&channel.feed_url(site, "atom.xml"))
+    ));
+    xml.push_str(&format!("  <id>{}</id>\n", escape_xml(&page_url)));
+    xml.push_str(&format!("  <updated>{}</updated>\n", channel_updated(channel).to_rfc3339()));
+    if !site.author.is_empty() {
+        xml.push_str(&format!("  <author><name>{}</name></author>\n", escape_xml(&site.author)));
+    }
+
+    for post in &channel.posts {
+        let url = post_url(post, site);
+        let entry_updated = updated(post).unwrap_or_default();
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!("    <title>{}</title>\n", escape_xml(&post.metadata.title)));
+        xml.push_str(&format!("    <link href=\"{}\"/>\n", escape_xml(&url)));
+        xml.push_str(&format!("    <id>{}</id>\n", escape_xml(&url)));
+        if let Some(date) = published(post) {
+            xml.push_str(&format!("    <published>{}</published>\n", date.to_rfc3339()));
+        }
+        xml.push_str(&format!("    <updated>{}</updated>\n", entry_updated.to_rfc3339()));
+        let author = post_author(post, site);
+        if !author.is_empty() {
+            xml.push_str(&format!("    <author><name>{}</name></author>\n", escape_xml(author)));
+        }
+        for category in post.metadata.categories.iter().flatten() {
+            xml.push_str(&format!("    <category term=\"{}\"/>\n", escape_xml(category)));
+        }
+        let excerpt = post.excerpt_text();
+        if !excerpt.is_empty() {
+            xml.push_str(&format!("    <summary>{}</summary>\n", escape_xml(&excerpt)));
+        }
+        xml.push_str(&format!(
+            "    <content type=\"html\">{}</content>\n",
+            escape_xml(&absolutize_urls(&post.html_content, post, site))
+        ));
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+fn render_json_feed(channel: &Channel, site: &SiteConfig) -> Result<String> {
+    let items: Vec<serde_json::Value> = channel
+        .posts
+        .iter()
+        .map(|post| {
+            let url = post_url(post, site);
+            let mut item = json!({
+                "id": url,
+                "url": url,
+                "title": post.metadata.title,
+                "content_html": absolutize_urls(&post.html_content, post, site),
+            });
+            let excerpt = post.excerpt_text();
+            if !excerpt.is_empty() {
+                item["summary"] = json!(excerpt);
+            }
+            if let Some(date) = published(post) {
+                item["date_published"] = json!(date.to_rfc3339());
+            }
+            if let Some(date) = updated(post) {
+                item["date_modified"] = json!(date.to_rfc3339());
+            }
+            let author = post_author(post, site);
+            if !author.is_empty() {
+                item["authors"] = json!([{ "name": author }]);
+            }
+            let tags: Vec<&String> = post
+                .metadata
+                .categories
+                .iter()
+                .flatten()
+                .chain(post.metadata.tags.iter().flatten())
+                .collect();
+            if !tags.is_empty() {
+                item["tags"] = json!(tags);
+            }
+            item
+        })
+        .collect();
+
+    let mut feed = json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": channel.title,
+        "home_page_url": site.absolute_url(&channel.page_path),
+        "feed_url": channel.feed_url(site, "feed.json"),
+        "language": site.language,
+        "items": items,
+    });
+    if !site.description.is_empty() {
+        feed["description"] = json!(site.description);
+    }
+    if !site.author.is_empty() {
+        feed["authors"] = json!([{ "name": site.author }]);
+    }
+
+    serde_json::to_string_pretty(&feed).context("JSON Feed 序列化失败")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PostMetadata;
+
+    fn sample_index() -> PostIndex {
+        let mut metadata = PostMetadata::new();
+        metadata.title = "Rust & <Dioxus>".to_string();
+        metadata.date = crate::parse_date("2025-11-23");
+        metadata.tags = Some(vec!["Rust".to_string(), "博客".to_string()]);
+        metadata.categories = Some(vec!["技术".to_string()]);
+        metadata.summary = Some("摘要".to_string());
+        PostIndex::from_posts(vec![Post {
+            metadata,
+            file_path: "2025-11-23-hello.md".to_string(),
+            content: String::new(),
+            html_content: "<p>正文 ]]> 结束</p>".to_string(),
+            ..Post::default()
+        }])
+        .unwrap()
+    }
+
+    fn sample_site() -> SiteConfig {
+        SiteConfig {
+            base_url: "https://example.com".to_string(),
+            author: "Sisyphus".to_string(),
+            ..SiteConfig::default()
+        }
+    }
+
+    fn find<'a>(files: &'a [FeedFile], path: &str) -> &'a str {
+        &files.iter().find(|f| f.path == path).unwrap().content
+    }
+
+    #[test]
+    fn test_generate_feeds() {
+        let files = generate_feeds(&sample_index(), &sample_site()).unwrap();
+
+        let rss = find(&files, "feed.xml");
+        assert!(rss.contains("<title>Rust &amp; &lt;Dioxus&gt;</title>"));
+        assert!(rss.contains("<link>https://example.com/blog/hello</link>"));
+        assert!(rss.contains("<pubDate>Sun, 23 Nov 2025 00:00:00 +0000</pubDate>"));
+        assert!(rss.contains("<category>技术</category>"));
+        assert!(rss.contains("<dc:creator>Sisyphus</dc:creator>"));
+        assert!(rss.contains("<![CDATA[<p>正文 ]]]]><![CDATA[> 结束</p>]]>"));
+
+        let atom = find(&files, "atom.xml");
+        assert!(atom.contains("<updated>2025-11-23T00:00:00+00:00</updated>"));
+        assert!(atom.contains("&lt;p&gt;正文"));
+
+        let json: serde_json::Value = serde_json::from_str(find(&files, "feed.json")).unwrap();
+        assert_eq!(json["items"][0]["url"], "https://example.com/blog/hello");
+        assert_eq!(json["items"][0]["tags"][0], "技术");
+    }
+
+    #[test]
+    fn test_per_tag_feeds() {
+        let files = generate_feeds(&sample_index(), &sample_site()).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert!(paths.contains(&"tags/rust/feed.xml"));
+        assert!(paths.contains(&"tags/博客/atom.xml"));
+        assert!(paths.contains(&"tags/博客/feed.json"));
+
+        let tag_rss = find(&files, "tags/博客/feed.xml");
+        assert!(tag_rss.contains("https://example.com/blog/tag/%E5%8D%9A%E5%AE%A2"));
+    }
+
+    #[test]
+    fn test_feed_urls_are_encoded_and_absolute() {
+        let mut index = sample_index();
+        index.posts[0].html_content = concat!(
+            "<p><a href=\"other\">相对</a> <a href=\"/blog/tag/Rust\">站内</a> <a href=\"#note\">锚点</a> ",
+            "<a href=\"https://example.org/x\">外链</a> <img src=\"../assets/a.png\" alt=\"\"></p>"
+        )
+        .to_string();
+        let files = generate_feeds(&index, &sample_site()).unwrap();
+
+        let tag_rss = find(&files, "tags/博客/feed.xml");
+        assert!(tag_rss.contains("<atom:link href=\"https://example.com/tags/%E5%8D%9A%E5%AE%A2/feed.xml\""));
+        assert!(find(&files, "tags/博客/atom.xml").contains("href=\"https://example.com/tags/%E5%8D%9A%E5%AE%A2/atom.xml\""));
+        let json: serde_json::Value = serde_json::from_str(find(&files, "tags/博客/feed.json")).unwrap();
+        assert_eq!(json["feed_url"], "https://example.com/tags/%E5%8D%9A%E5%AE%A2/feed.json");
+
+        let content = json["items"][0]["content_html"].as_str().unwrap();
+        for url in [
+            "href=\"https://example.com/blog/other\"",
+            "href=\"https://example.com/blog/tag/Rust\"",
+            "href=\"https://example.com/blog/hello#note\"",
+            "href=\"https://example.org/x\"",
+            "src=\"https://example.com/assets/a.png\"",
+        ] {
+            assert!(content.contains(url), "缺少 {}: {}", url, content);
+        }
+        assert!(find(&files, "feed.xml").contains("src=\"https://example.com/assets/a.png\""));
+    }
+
+    #[test]
+    fn test_tag_feed_dirs_do_not_collide() {
+        let post = |file: &str, tag: &str| Post {
+            metadata: PostMetadata {
+                title: file.to_string(),
+                date: NaiveDate::from_ymd_opt(2025, 11, 23),
+                tags: Some(vec![tag.to_string()]),
+                ..PostMetadata::default()
+            },
+            file_path: file.to_string(),
+            ..Post::default()
+        };
+        let dirs = |tags: &[&str]| -> Vec<(String, String)> {
+            let posts = tags.iter().enumerate().map(|(i, tag)| post(&format!("{}.md", i), tag));
+            let index = PostIndex::from_posts(posts).unwrap();
+            generate_feeds(&index, &sample_site())
+                .unwrap()
+                .into_iter()
+                .filter(|f| f.path.starts_with("tags/") && f.path.ends_with("/feed.xml"))
+                .map(|f| {
+                    let tag = f.content.split("标签: ").nth(1).unwrap().split('<').next().unwrap().to_string();
+                    (tag, f.path.trim_end_matches("/feed.xml").to_string())
+                })
+                .collect()
+        };
+        let hashed = |base: &str, tag: &str| format!("tags/{}-{:08x}", base, fnv1a(tag));
+
+        let before = dirs(&["C", "C++", "c-2", "Rust", "rust"]);
+        assert_eq!(
+            before,
+            vec![
+                ("C".to_string(), hashed("c", "C")),
+                ("C++".to_string(), hashed("c", "C++")),
+                ("Rust".to_string(), hashed("rust", "Rust")),
+                ("c-2".to_string(), "tags/c-2".to_string()),
+                ("rust".to_string(), "tags/rust".to_string()),
+            ]
+        );
+
+        // 新增一个同目录的标签不影响已有订阅源的地址
+        let after = dirs(&["C", "C++", "c-2", "Rust", "rust", "C#"]);
+        for entry in &before {
+            assert!(after.contains(entry), "{:?}", entry);
+        }
+    }
+}
@@ -0,0 +1,375 @@
+//! Frontmatter 的识别与解析
+//!
+//! 支持三种格式：
+//! - YAML：位于单独成行的 `---` 之间
+//! - TOML：位于单独成行的 `+++` 之间
+//! - JSON：第一行只有 `{` 的 JSON 对象
+//!
+//! 分隔符（包括 JSON 的开头 `{`）必须独占一行，YAML 字符串或正文中的 `---`（如分隔线）不会提前结束 Frontmatter。
+//! 出错时返回 [`FrontmatterError`]，其中的行号、列号相对于整个文件（从 1 开始）。
+
+use crate::PostMetadata;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use thiserror::Error;
+
+/// Frontmatter 格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontmatterFormat {
+    Yaml,
+    Toml,
+    Json,
+}
+
+impl FrontmatterFormat {
+    /// 根据文件开头识别格式，没有 Frontmatter 时返回 `None`
+    fn detect(content: &str) -> Option<Self> {
+        let first_line = content.lines().next()?.trim_end();
+        match first_line {
+            "---" => Some(Self::Yaml),
+            "+++" => Some(Self::Toml),
+            "{" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    fn delimiter(self) -> &'static str {
+        match self {
+            Self::Yaml => "---",
+            Self::Toml => "+++",
+            Self::Json => "}",
+        }
+    }
+}
+
+impl fmt::Display for FrontmatterFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Yaml => "YAML",
+            Self::Toml => "TOML",
+            Self::Json => "JSON",
+        })
+    }
+}
+
+/// Frontmatter 解析错误，附带文件路径和出错位置
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{file}:{line}:{column}: {kind}")]
+pub struct FrontmatterError {
+    /// 文件路径
+    pub file: String,
+    /// 行号（从 1 开始）
+    pub line: usize,
+    /// 列号（从 1 开始，按字符计）
+    pub column: usize,
+    /// 错误类型
+    pub kind: FrontmatterErrorKind,
+}
+
+/// Frontmatter 错误类型
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FrontmatterErrorKind {
+    /// 找不到独占一行的结束分隔符
+    #[error("{format} Frontmatter 缺少结束分隔符 `{}`", format.delimiter())]
+    Unclosed { format: FrontmatterFormat },
+    /// 语法错误或字段类型不匹配
+    #[error("{format} Frontmatter 解析失败：{message}")]
+    Invalid { format: FrontmatterFormat, message: String },
+}
+
+/// Frontmatter 中的一个顶层字段
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontmatterField {
+    /// 字段名
+    pub key: String,
+    /// 字段值（TOML 日期时间转为字符串）
+    pub value: serde_json::Value,
+    /// 字段所在行（从 1 开始）
+    pub line: usize,
+}
+
+/// 分离 Frontmatter 和正文
+///
+/// `file` 只用于错误信息。没有 Frontmatter 时返回默认元数据和完整内容
+pub fn parse_frontmatter(content: &str, file: &str) -> Result<(PostMetadata, String), FrontmatterError> {
+    // 去除可能的 BOM
+    let content = content.trim_start_matches('\u{feff}');
+
+    Ok(match parse_block(content, file)? {
+        Some((metadata, body)) => (metadata, body.to_string()),
+        None => (PostMetadata::new(), content.to_string()),
+    })
+}
+
+/// 按原样读取 Frontmatter 的顶层字段及其所在行，供 `blog lint` 检查未知字段和取值
+pub fn frontmatter_fields(content: &str, file: &str) -> Result<Vec<FrontmatterField>, FrontmatterError> {
+    let content = content.trim_start_matches('\u{feff}');
+    let Some((map, body)) = parse_block::<serde_json::Map<String, serde_json::Value>>(content, file)? else {
+        return Ok(Vec::new());
+    };
+
+    // 字段所在行：Frontmatter 范围内第一个以该字段名（可带引号）加 `:` 或 `=` 开头的行
+    let header = &content[..content.len() - body.len()];
+    let mut fields: Vec<_> = map
+        .into_iter()
+        .map(|(key, value)| {
+            let line = header
+                .lines()
+                .position(|line| {
+                    let line = line.trim_start();
+                    let line = line.strip_prefix(['"', '\'']).unwrap_or(line);
+                    line.strip_prefix(key.as_str()).is_some_and(|rest| {
+                        let rest = rest.strip_prefix(['"', '\'']).unwrap_or(rest).trim_start();
+                        rest.starts_with([':', '='])
+                    })
+                })
+                .map_or(1, |i| i + 1);
+            FrontmatterField { key, value, line }
+        })
+        .collect();
+    fields.sort_by_key(|field| field.line);
+    Ok(fields)
+}
+
+/// 识别并解析 Frontmatter，返回解析结果和正文；没有 Frontmatter 时返回 `None`
+fn parse_block<'a, T: DeserializeOwned>(
+    content: &'a str,
+    file: &str,
+) -> Result<Option<(T, &'a str)>, FrontmatterError> {
+    let Some(format) = FrontmatterFormat::detect(content) else {
+        return Ok(None);
+    };
+    let error = |line, column, kind| FrontmatterError {
+        file: file.to_string(),
+        line,
+        column,
+        kind,
+    };
+
+    if format == FrontmatterFormat::Json {
+        // JSON 对象自带边界，读完第一个完整的值即可
+        let mut stream = serde_json::Deserializer::from_str(content).into_iter::<T>();
+        return match stream.next() {
+            Some(Ok(value)) => Ok(Some((value, strip_line_break(&content[stream.byte_offset()..])))),
+            Some(Err(e)) if e.is_eof() => Err(error(1, 1, FrontmatterErrorKind::Unclosed { format })),
+            Some(Err(e)) => Err(error(
+                e.line(),
+                e.column(),
+                FrontmatterErrorKind::Invalid { format, message: strip_location(&e.to_string()) },
+            )),
+            None => Err(error(1, 1, FrontmatterErrorKind::Unclosed { format })),
+        };
+    }
+
+    // 第一行是开始分隔符，查找下一个独占一行的相同分隔符
+    let body_start = content.find('\n').map_or(content.len(), |i| i + 1);
+    let mut offset = body_start;
+    let mut end = None;
+    for line in content[body_start..].split_inclusive('\n') {
+        if line.trim_end() == format.delimiter() {
+            end = Some((offset, offset + line.len()));
+            break;
+        }
+        offset += line.len();
+    }
+    let Some((block_end, rest_start)) = end else {
+        return Err(error(1, 1, FrontmatterErrorKind::Unclosed { format }));
+    };
+    let block = &content[body_start..block_end];
+
+    // 块内的位置加上开始分隔符所在的一行
+    let invalid = |(line, column): (usize, usize), message: String| {
+        error(line + 1, column, FrontmatterErrorKind::Invalid { format, message })
+    };
+    let value = match format {
+        _ if block.trim().is_empty() => serde_json::from_value(serde_json::Value::Object(Default::default()))
+            .map_err(|e| invalid((1, 1), e.to_string()))?,
+        FrontmatterFormat::Yaml => serde_yaml::from_str(block).map_err(|e| {
+            let position = e.location().map_or((1, 1), |l| (l.line(), l.column()));
+            invalid(position, strip_location(&e.to_string()))
+        })?,
+        _ => {
+            let table: toml::Table = toml::from_str(block).map_err(|e| {
+                let position = e.span().map_or((1, 1), |span| line_column(block, span.start));
+                invalid(position, e.message().to_string())
+            })?;
+            serde_json::from_value(toml_to_json(toml::Value::Table(table)))
+                .map_err(|e| invalid((1, 1), e.to_string()))?
+        }
+    };
+
+    Ok(Some((value, &content[rest_start..])))
+}
+
+/// 把 TOML 值转换为 JSON 值，日期时间转为字符串，以便复用 [`PostMetadata`] 的日期解析
+fn toml_to_json(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(s) => serde_json::Value::String(s),
+        toml::Value::Integer(i) => i.into(),
+        toml::Value::Float(f) => f.into(),
+        toml::Value::Boolean(b) => b.into(),
+        toml::Value::Datetime(dt) => dt.to_string().into(),
+        toml::Value::Array(array) => array.into_iter().map(toml_to_json).collect(),
+        toml::Value::Table(table) => {
+            table.into_iter().map(|(key, value)| (key, toml_to_json(value))).collect()
+        }
+    }
+}
+
+/// 字节偏移对应的行号和列号（从 1 开始）
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
+}
+
+/// 去掉 serde_yaml / serde_json 附加在消息末尾的位置，位置由 [`FrontmatterError`] 单独给出
+fn strip_location(message: &str) -> String {
+    match message.rfind(" at line ") {
+        Some(i) => message[..i].to_string(),
+        None => message.to_string(),
+    }
+}
+
+/// 去掉 JSON 对象后紧跟的换行
+fn strip_line_break(body: &str) -> &str {
+    let body = body.trim_start_matches([' ', '\t']);
+    body.strip_prefix("\r\n")
+        .or_else(|| body.strip_prefix('\n'))
+        .unwrap_or(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_yaml_frontmatter() {
+        let content = r#"---
+title: "测试文章"
+date: 2025-11-23
+tags: ["Rust", "Dioxus"]
+---
+
+这是文章正文内容。
+"#;
+        let (metadata, body) = parse_frontmatter(content, "a.md").unwrap();
+        assert_eq!(metadata.title, "测试文章");
+        assert_eq!(body, "\n这是文章正文内容。\n");
+    }
+
+    #[test]
+    fn test_delimiter_must_be_on_its_own_line() {
+        let content = "---\ntitle: \"a --- b\"\nsummary: x---y\n---\n正文\n\n---\n\n分隔线之后\n";
+        let (metadata, body) = parse_frontmatter(content, "a.md").unwrap();
+        assert_eq!(metadata.title, "a --- b");
+        assert_eq!(metadata.summary.as_deref(), Some("x---y"));
+        assert_eq!(body, "正文\n\n---\n\n分隔线之后\n");
+
+        // 空的 Frontmatter
+        let (metadata, body) = parse_frontmatter("---\n---\n正文", "a.md").unwrap();
+        assert_eq!((metadata.title.as_str(), body.as_str()), ("未命名文章", "正文"));
+
+        // Windows 换行和分隔符后的空白
+        let (metadata, body) = parse_frontmatter("\u{feff}---\r\ntitle: a\r\n--- \r\n正文", "a.md").unwrap();
+        assert_eq!(metadata.title, "a");
+        assert_eq!(body, "正文");
+
+        // 开头的 ---- 不是 Frontmatter
+        let (metadata, body) = parse_frontmatter("----\n正文", "a.md").unwrap();
+        assert_eq!(metadata.title, "未命名文章");
+        assert_eq!(body, "----\n正文");
+    }
+
+    #[test]
+    fn test_toml_frontmatter() {
+        let content = "+++\ntitle = \"TOML 文章\"\ndate = 2025-11-23\nupdated = 2025-11-24T08:30:00\ntags = [\"Rust\"]\ndraft = true\n+++\n正文\n";
+        let (metadata, body) = parse_frontmatter(content, "a.md").unwrap();
+        assert_eq!(metadata.title, "TOML 文章");
+        assert_eq!(metadata.date, NaiveDate::from_ymd_opt(2025, 11, 23));
+        assert_eq!(metadata.updated.unwrap().to_string(), "2025-11-24 08:30:00");
+        assert_eq!(metadata.tags, Some(vec!["Rust".to_string()]));
+        assert!(metadata.draft);
+        assert_eq!(body, "正文\n");
+    }
+
+    #[test]
+    fn test_json_frontmatter() {
+        let content = "{\n  \"title\": \"JSON 文章\",\n  \"date\": \"2025-11-23\",\n  \"tags\": [\"a\", \"b\"]\n}\n正文 {不是 JSON}\n";
+        let (metadata, body) = parse_frontmatter(content, "a.md").unwrap();
+        assert_eq!(metadata.title, "JSON 文章");
+        assert_eq!(metadata.date, NaiveDate::from_ymd_opt(2025, 11, 23));
+        assert_eq!(body, "正文 {不是 JSON}\n");
+    }
+
+    #[test]
+    fn test_body_starting_with_brace_is_not_json() {
+        for content in ["{{ template }}\n正文\n", "{\"title\": \"一行\"}\n正文\n", "{ 普通段落\n"] {
+            let (metadata, body) = parse_frontmatter(content, "a.md").unwrap();
+            assert_eq!(metadata, PostMetadata::new());
+            assert_eq!(body, content);
+        }
+    }
+
+    #[test]
+    fn test_unclosed_frontmatter() {
+        let err = parse_frontmatter("---\ntitle: a\n正文\n", "blogs/a.md").unwrap_err();
+        assert_eq!(err.kind, FrontmatterErrorKind::Unclosed { format: FrontmatterFormat::Yaml });
+        assert_eq!(err.to_string(), "blogs/a.md:1:1: YAML Frontmatter 缺少结束分隔符 `---`");
+
+        let err = parse_frontmatter("+++\ntitle = \"a\"\n", "a.md").unwrap_err();
+        assert_eq!(err.kind, FrontmatterErrorKind::Unclosed { format: FrontmatterFormat::Toml });
+
+        let err = parse_frontmatter("{\n\"title\": \"a\"", "a.md").unwrap_err();
+        assert_eq!(err.kind, FrontmatterErrorKind::Unclosed { format: FrontmatterFormat::Json });
+    }
+
+    #[test]
+    fn test_error_positions() {
+        // YAML：第 3 行的缩进错误
+        let err = parse_frontmatter("---\ntitle: a\n  tags: [x\n---\n", "a.md").unwrap_err();
+        assert_eq!((err.file.as_str(), err.line), ("a.md", 3));
+        assert!(matches!(err.kind, FrontmatterErrorKind::Invalid { format: FrontmatterFormat::Yaml, .. }));
+        assert!(!err.to_string().contains(" at line "), "{}", err);
+
+        // YAML：字段类型错误
+        let err = parse_frontmatter("---\ntitle: a\ndraft: maybe\n---\n", "a.md").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 8));
+
+        // TOML：第 3 行第 4 列缺少 `=`
+        let err = parse_frontmatter("+++\ntitle = \"a\"\nda te = 1\n+++\n", "a.md").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 4));
+        assert!(matches!(err.kind, FrontmatterErrorKind::Invalid { format: FrontmatterFormat::Toml, .. }));
+
+        // JSON：第 3 行缺少逗号
+        let err = parse_frontmatter("{\n  \"title\": \"a\"\n  \"draft\": true\n}\n", "a.md").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert!(matches!(err.kind, FrontmatterErrorKind::Invalid { format: FrontmatterFormat::Json, .. }));
+    }
+
+    #[test]
+    fn test_frontmatter_fields() {
+        let content = "---\ntitle: a\ndate: 2025-13-01\ntags:\n  - x\nauthr: b\n---\n正文\nauthr: c\n";
+        let fields = frontmatter_fields(content, "a.md").unwrap();
+        let keys: Vec<_> = fields.iter().map(|f| (f.key.as_str(), f.line)).collect();
+        assert_eq!(keys, vec![("title", 2), ("date", 3), ("tags", 4), ("authr", 6)]);
+        assert_eq!(fields[1].value, "2025-13-01");
+
+        let fields = frontmatter_fields("+++\ndate = 2025-11-23\n+++\n", "a.md").unwrap();
+        assert_eq!((fields[0].value.as_str(), fields[0].line), (Some("2025-11-23"), 2));
+
+        let fields = frontmatter_fields("{\n  \"title\": \"a\"\n}\n", "a.md").unwrap();
+        assert_eq!(fields[0].line, 2);
+
+        assert!(frontmatter_fields("正文", "a.md").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_line_column() {
+        assert_eq!(line_column("ab\n标题x", 0), (1, 1));
+        assert_eq!(line_column("ab\n标题x", 3), (2, 1));
+        assert_eq!(line_column("ab\n标题x", 9), (2, 3));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::feed::escape_xml;
+use anyhow::{Context, Result};
+use std::sync::OnceLock;
+use syntect::highlighting::ThemeSet;
+use syntect::html::{css_for_theme_with_class_style, ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+/// 高亮使用的 CSS 类名风格：`hl-keyword`、`hl-string` 等，避免与 Tailwind 冲突
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+
+/// 亮色与暗色模式使用的主题（syntect 内置）
+const LIGHT_THEME: &str = "InspiredGitHub";
+const DARK_THEME: &str = "base16-ocean.dark";
+
+/// 内置语法定义，加载较慢，整个构建过程只加载一次
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// 把代码块渲染为带高亮类名的 HTML
+///
+/// `lang` 为围栏代码块的语言标记（如 `rust`、`rust,ignore`），
+/// 无法识别的语言按纯文本输出
+pub fn highlight_code(code: &str, lang: &str) -> String {
+    let lang = lang.split([',', ' ']).next().unwrap_or_default().trim();
+    let code_class = if lang.is_empty() {
+        String::new()
+    } else {
+        format!(" class=\"language-{}\"", escape_xml(lang))
+    };
+
+    let syntaxes = syntax_set();
+    let highlighted = syntaxes
+        .find_syntax_by_token(lang)
+        .filter(|_| !lang.is_empty())
+        .and_then(|syntax| {
+            let mut generator =
+                ClassedHTMLGenerator::new_with_class_style(syntax, syntaxes, CLASS_STYLE);
+            for line in LinesWithEndings::from(code) {
+                generator
+                    .parse_html_for_line_which_includes_newline(line)
+                    .ok()?;
+            }
+            Some(generator.finalize())
+        })
+        .unwrap_or_else(|| escape_xml(code));
+
+    format!(
+        "<pre class=\"code-block hl-code\"><code{}>{}</code></pre>\n",
+        code_class, highlighted
+    )
+}
+
+/// 生成高亮主题样式：亮色主题直接生效，暗色主题限定在 `.dark` 之下
+pub fn highlight_css() -> Result<String> {
+    let themes = ThemeSet::load_defaults();
+    let theme = |name: &str| {
+        themes
+            .themes
+            .get(name)
+            .with_context(|| format!("找不到代码高亮主题: {}", name))
+    };
+
+    let light = css_for_theme_with_class_style(theme(LIGHT_THEME)?, CLASS_STYLE)
+        .context("生成亮色高亮样式失败")?;
+    let dark = css_for_theme_with_class_style(theme(DARK_THEME)?, CLASS_STYLE)
+        .context("生成暗色高亮样式失败")?;
+
+    Ok(format!("{}\n{}", light, scope_css(&dark, ".dark")))
+}
+
+/// 给样式表中的每个选择器加上父级选择器
+fn scope_css(css: &str, scope: &str) -> String {
+    let mut scoped = String::with_capacity(css.len());
+    let mut in_comment = false;
+    for line in css.lines() {
+        let trimmed = line.trim_start();
+        if in_comment || trimmed.starts_with("/*") {
+            in_comment = !trimmed.contains("*/");
+        } else if let Some(selectors) = trimmed.strip_suffix('{') {
+            let selectors = selectors
+                .split(',')
+                .map(|selector| format!("{} {}", scope, selector.trim()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            scoped.push_str(&selectors);
+            scoped.push_str(" {\n");
+            continue;
+        }
+        scoped.push_str(line);
+        scoped.push('\n');
+    }
+    scoped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_known_language() {
+        let html = highlight_code("fn main() {}\n", "rust");
+        assert!(html.starts_with(r#"<pre class="code-block hl-code"><code class="language-rust">"#));
+        assert!(html.contains(r#"<span class="hl-storage hl-type hl-function hl-rust">fn</span>"#));
+    }
+
+    #[test]
+    fn test_highlight_unknown_language_is_escaped() {
+        let html = highlight_code("<b>&</b>\n", "no-such-language");
+        assert!(html.contains("&lt;b&gt;&amp;&lt;/b&gt;"));
+        assert!(!html.contains("hl-source"));
+
+        let html = highlight_code("plain\n", "");
+        assert_eq!(html, "<pre class=\"code-block hl-code\"><code>plain\n</code></pre>\n");
+    }
+
+    #[test]
+    fn test_highlight_css_scopes_dark_theme() {
+        let css = highlight_css().unwrap();
+        assert!(css.contains("\n.hl-code {"));
+        assert!(css.contains(".dark .hl-code {"));
+    }
+
+    #[test]
+    fn test_scope_css() {
+        let css = "/*\n * theme\n */\n.a, .b .c {\n color: red;\n}\n";
+        assert_eq!(
+            scope_css(css, ".dark"),
+            "/*\n * theme\n */\n.dark .a, .dark .b .c {\n color: red;\n}\n"
+        );
+    }
+}
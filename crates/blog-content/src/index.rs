@@ -0,0 +1,694 @@
+use crate::site::{is_safe_path_segment, RESERVED_SLUGS};
+use crate::slugify;
+use crate::Post;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+#[cfg(feature = "markdown")]
+use crate::{date_from_filename, is_bundle_index, process_post_file};
+#[cfg(feature = "markdown")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "markdown")]
+use walkdir::WalkDir;
+
+/// 某篇文章在时间线上的相邻文章
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AdjacentPosts<'a> {
+    /// 上一篇（更早发布）
+    pub previous: Option<&'a Post>,
+    /// 下一篇（更晚发布）
+    pub next: Option<&'a Post>,
+}
+
+/// 列表的一页文章
+#[derive(Debug, Clone, Default)]
+pub struct Page<'a> {
+    /// 本页的文章（按日期从新到旧）
+    pub posts: Vec<&'a Post>,
+    /// 当前页码（从 1 开始）
+    pub page: usize,
+    /// 总页数（没有文章时为 1）
+    pub total_pages: usize,
+    /// 文章总数
+    pub total_posts: usize,
+}
+
+impl Page<'_> {
+    /// 页码是否在有效范围内
+    pub fn is_valid(&self) -> bool {
+        self.page >= 1 && self.page <= self.total_pages
+    }
+}
+
+/// 文章索引，包含所有文章和分类信息
+///
+/// build.rs 将其序列化为 `posts_index.json`，运行时再反序列化回来
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostIndex {
+    /// 所有文章列表
+    pub posts: Vec<Post>,
+    /// 标签到文章索引的映射
+    pub tags: HashMap<String, Vec<usize>>,
+    /// 分类到文章索引的映射
+    pub categories: HashMap<String, Vec<usize>>,
+    /// 按日期排序的文章索引（从新到旧）
+    pub sorted_by_date: Vec<usize>,
+    /// slug 到文章索引的映射（精确匹配，slug 唯一）
+    #[serde(default)]
+    pub slugs: HashMap<String, usize>,
+}
+
+impl PostIndex {
+    /// 创建空的 PostIndex
+    pub fn new() -> Self {
+        Self {
+            posts: Vec::new(),
+            tags: HashMap::new(),
+            categories: HashMap::new(),
+            sorted_by_date: Vec::new(),
+            slugs: HashMap::new(),
+        }
+    }
+
+    /// 从文章列表构建索引：跳过草稿，建立 slug/标签/分类映射并按日期排序
+    ///
+    /// 两篇文章解析出相同的 slug 时返回错误
+    pub fn from_posts(posts: impl IntoIterator<Item = Post>) -> Result<Self> {
+        let mut index = Self::new();
+
+        for post in posts {
+            // 跳过草稿
+            if post.metadata.is_draft() {
+                continue;
+            }
+
+            let post_idx = index.posts.len();
+
+            // 构建 slug 索引，重复、不规范或与固定路由冲突的 slug 直接报错；
+            // slug 会用作输出文件路径，不规范的 slug（如 `../x`）可能写到输出目录之外
+            let slug = post.slug();
+            if slug.is_empty() || slugify(&slug) != slug {
+                bail!("{} 的 slug '{}' 不是规范形式，应为 '{}'", post.file_path, slug, slugify(&slug));
+            }
+            if RESERVED_SLUGS.contains(&slug.as_str()) {
+                bail!("{} 的 slug '{}' 与 /blog/{} 页面冲突，请换一个 slug", post.file_path, slug, slug);
+            }
+            if let Some(&existing) = index.slugs.get(&slug) {
+                bail!(
+                    "slug 重复: '{}' 同时被 {} 和 {} 使用",
+                    slug,
+                    index.posts[existing].file_path,
+                    post.file_path
+                );
+            }
+            index.slugs.insert(slug, post_idx);
+
+            // 标签和分类名会成为输出目录名，不能跳出所在目录
+            let taxonomies = [("标签", &post.metadata.tags), ("分类", &post.metadata.categories)];
+            for (kind, names) in taxonomies {
+                if let Some(name) = names.iter().flatten().find(|name| !is_safe_path_segment(name)) {
+                    bail!("{} 的{} '{}' 不能为空、. 或 ..，也不能包含 / 或 \\", post.file_path, kind, name);
+                }
+            }
+
+            // 构建标签索引
+            if let Some(ref tags) = post.metadata.tags {
+                for tag in tags {
+                    index.tags.entry(tag.clone()).or_default().push(post_idx);
+                }
+            }
+
+            // 构建分类索引
+            if let Some(ref categories) = post.metadata.categories {
+                for category in categories {
+                    index.categories.entry(category.clone()).or_default().push(post_idx);
+                }
+            }
+
+            index.posts.push(post);
+        }
+
+        // 按日期排序（从新到旧），没有日期的文章排在最后
+        let mut sorted: Vec<usize> = (0..index.posts.len()).collect();
+        sorted.sort_by(|&a, &b| {
+            let date_a = index.posts[a].metadata.date;
+            let date_b = index.posts[b].metadata.date;
+            match (date_a, date_b) {
+                (Some(date_a), Some(date_b)) => date_b.cmp(&date_a),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+        });
+        index.sorted_by_date = sorted;
+
+        // 标签/分类下的文章同样按日期排序
+        let mut rank = vec![0; index.posts.len()];
+        for (position, &idx) in index.sorted_by_date.iter().enumerate() {
+            rank[idx] = position;
+        }
+        for indices in index.tags.values_mut().chain(index.categories.values_mut()) {
+            indices.sort_by_key(|&idx| rank[idx]);
+        }
+
+        Ok(index)
+    }
+
+    /// 从 build.rs 生成的 `posts_index.json` 内容解析索引
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).context("posts_index.json 解析失败")
+    }
+
+    /// 序列化为 `posts_index.json` 内容
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("文章索引序列化失败")
+    }
+
+    /// 根据标签获取文章列表
+    pub fn get_posts_by_tag(&self, tag: &str) -> Vec<&Post> {
+        self.tags
+            .get(tag)
+            .map(|indices| {
+                indices
+                    .iter()
+                    .filter_map(|&idx| self.posts.get(idx))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 根据分类获取文章列表
+    pub fn get_posts_by_category(&self, category: &str) -> Vec<&Post> {
+        self.categories
+            .get(category)
+            .map(|indices| {
+                indices
+                    .iter()
+                    .filter_map(|&idx| self.posts.get(idx))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 所有标签及其文章数，按文章数从多到少、名称升序排列
+    pub fn tag_counts(&self) -> Vec<(&str, usize)> {
+        count_terms(&self.tags)
+    }
+
+    /// 所有分类及其文章数，按文章数从多到少、名称升序排列
+    pub fn category_counts(&self) -> Vec<(&str, usize)> {
+        count_terms(&self.categories)
+    }
+
+    /// 获取最近的文章（按日期排序）
+    pub fn get_recent_posts(&self, count: usize) -> Vec<&Post> {
+        self.sorted_by_date
+            .iter()
+            .take(count)
+            .filter_map(|&idx| self.posts.get(idx))
+            .collect()
+    }
+
+    /// 获取全部文章（按日期排序）的第 `page` 页
+    pub fn get_page(&self, page: usize, per_page: usize) -> Page<'_> {
+        self.paginate(&self.sorted_by_date, page, per_page)
+    }
+
+    /// 获取某标签下文章的第 `page` 页
+    pub fn get_tag_page(&self, tag: &str, page: usize, per_page: usize) -> Page<'_> {
+        let indices = self.tags.get(tag).map(Vec::as_slice).unwrap_or_default();
+        self.paginate(indices, page, per_page)
+    }
+
+    /// 获取某分类下文章的第 `page` 页
+    pub fn get_category_page(&self, category: &str, page: usize, per_page: usize) -> Page<'_> {
+        let indices = self.categories.get(category).map(Vec::as_slice).unwrap_or_default();
+        self.paginate(indices, page, per_page)
+    }
+
+    /// 对已排序的文章索引分页，页码从 1 开始，越界时返回空页
+    fn paginate(&self, indices: &[usize], page: usize, per_page: usize) -> Page<'_> {
+        let per_page = per_page.max(1);
+        let total_posts = indices.len();
+        let total_pages = total_posts.div_ceil(per_page).max(1);
+
+        let posts = if page >= 1 {
+            indices
+                .iter()
+                .skip((page - 1) * per_page)
+                .take(per_page)
+                .filter_map(|&idx| self.posts.get(idx))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Page {
+            posts,
+            page,
+            total_pages,
+            total_posts,
+        }
+    }
+
+    /// 获取所有文章
+    pub fn get_all_posts(&self) -> &[Post] {
+        &self.posts
+    }
+
+    /// 根据 slug 精确查找文章
+    pub fn get_post_by_slug(&self, slug: &str) -> Option<&Post> {
+        self.slugs.get(slug).and_then(|&idx| self.posts.get(idx))
+    }
+
+    /// 获取文章在 `sorted_by_date` 中的相邻文章
+    pub fn get_adjacent_posts(&self, slug: &str) -> AdjacentPosts<'_> {
+        self.adjacent_in(slug, &self.sorted_by_date)
+    }
+
+    /// 获取文章在同一分类内的相邻文章（同样按日期排序）
+    pub fn get_adjacent_posts_in_category(&self, slug: &str, category: &str) -> AdjacentPosts<'_> {
+        let Some(&post_idx) = self.slugs.get(slug) else {
+            return AdjacentPosts::default();
+        };
+        let in_category = |idx: &usize| {
+            self.posts[*idx]
+                .metadata
+                .categories
+                .as_ref()
+                .is_some_and(|categories| categories.iter().any(|c| c == category))
+        };
+
+        // 单次遍历按日期排序的列表，记录上一篇同分类文章
+        let mut newer = None;
+        let mut ordered = self.sorted_by_date.iter().copied().filter(in_category);
+        while let Some(idx) = ordered.next() {
+            if idx == post_idx {
+                return AdjacentPosts {
+                    previous: ordered.next().and_then(|idx| self.posts.get(idx)),
+                    next: newer.and_then(|idx| self.posts.get(idx)),
+                };
+            }
+            newer = Some(idx);
+        }
+        AdjacentPosts::default()
+    }
+
+    /// 在给定的（从新到旧）顺序中查找相邻文章
+    fn adjacent_in(&self, slug: &str, ordered: &[usize]) -> AdjacentPosts<'_> {
+        let Some(&post_idx) = self.slugs.get(slug) else {
+            return AdjacentPosts::default();
+        };
+        let Some(position) = ordered.iter().position(|&idx| idx == post_idx) else {
+            return AdjacentPosts::default();
+        };
+
+        AdjacentPosts {
+            previous: ordered.get(position + 1).and_then(|&idx| self.posts.get(idx)),
+            next: position
+                .checked_sub(1)
+                .and_then(|p| ordered.get(p))
+                .and_then(|&idx| self.posts.get(idx)),
+        }
+    }
+}
+
+/// 统计标签/分类映射中每一项的文章数
+fn count_terms(terms: &HashMap<String, Vec<usize>>) -> Vec<(&str, usize)> {
+    let mut counts: Vec<(&str, usize)> = terms
+        .iter()
+        .map(|(name, indices)| (name.as_str(), indices.len()))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    counts
+}
+
+/// 标签云权重：把文章数线性映射到 `1..=levels` 级
+pub fn tag_cloud_weight(count: usize, min: usize, max: usize, levels: usize) -> usize {
+    if levels <= 1 || max <= min {
+        return 1;
+    }
+    let count = count.clamp(min, max);
+    1 + (count - min) * (levels - 1) / (max - min)
+}
+
+impl Default for PostIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 扫描 blogs 目录，解析并渲染所有文章，生成文章索引
+#[cfg(feature = "markdown")]
+pub fn scan_blogs_directory(path: &Path) -> Result<PostIndex> {
+    let mut posts = Vec::new();
+
+    for (file_path, relative_path) in post_files(path) {
+        // 处理 Markdown 文件
+        match process_post_file(&file_path, &relative_path) {
+            Ok(mut post) => {
+                prepare_post(&mut post, &relative_path);
+                posts.push(post);
+            }
+            Err(e) => {
+                eprintln!("警告：处理文件失败 {}: {:#}", file_path.display(), e);
+                // 继续处理其他文件
+            }
+        }
+    }
+
+    PostIndex::from_posts(posts)
+}
+
+/// blogs 目录（含子目录）中的所有文章，返回文件路径和相对于 blogs 目录的路径
+///
+/// 按路径排序，保证索引顺序稳定。页面包目录中除 `index.md` 以外的 Markdown 文件属于
+/// 该文章的资源，不单独成文
+#[cfg(feature = "markdown")]
+pub fn post_files(path: &Path) -> Vec<(PathBuf, String)> {
+    let files: Vec<(PathBuf, String)> = WalkDir::new(path)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("md"))
+        .map(|e| {
+            let relative_path = e.path().strip_prefix(path).unwrap_or(e.path());
+            let relative_path = relative_path.to_string_lossy().replace('\\', "/");
+            (e.into_path(), relative_path)
+        })
+        .collect();
+
+    let bundle_dirs: Vec<&Path> = files
+        .iter()
+        .filter(|(_, relative_path)| is_bundle_index(relative_path))
+        .filter_map(|(_, relative_path)| Path::new(relative_path).parent())
+        .collect();
+    let in_bundle = |relative_path: &str| {
+        !is_bundle_index(relative_path)
+            && bundle_dirs.iter().any(|dir| Path::new(relative_path).starts_with(dir))
+    };
+    files.iter().filter(|(_, relative_path)| !in_bundle(relative_path)).cloned().collect()
+}
+
+/// 补全依赖文件路径的元数据：相对路径、slug 以及文件名中的日期
+#[cfg(feature = "markdown")]
+fn prepare_post(post: &mut Post, relative_path: &str) {
+    post.metadata.slug = Some(post.metadata.get_slug(relative_path));
+    if post.metadata.date.is_none() {
+        // 如果没有日期，尝试从文件名（页面包为目录名）提取
+        post.metadata.date = date_from_filename(relative_path);
+    }
+    post.file_path = relative_path.to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PostMetadata;
+
+    #[cfg(feature = "markdown")]
+    const SAMPLE_BLOGS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../blogs");
+
+    #[test]
+    fn test_post_index() {
+        let index = PostIndex::new();
+        
+        // 测试空索引
+        assert_eq!(index.get_all_posts().len(), 0);
+        assert_eq!(index.get_recent_posts(5).len(), 0);
+    }
+
+    #[test]
+    fn test_post_index_from_json() {
+        let json = r#"{
+            "posts": [
+                {
+                    "title": "测试文章",
+                    "date": "2025-11-23",
+                    "tags": ["Rust"],
+                    "categories": null,
+                    "slug": "test-article",
+                    "draft": false,
+                    "updated": null,
+                    "file_path": "2025-11-23-test-article.md"
+                }
+            ],
+            "tags": { "Rust": [0] },
+            "categories": {},
+            "sorted_by_date": [0],
+            "slugs": { "test-article": 0 }
+        }"#;
+        let index = PostIndex::from_json(json).unwrap();
+        assert_eq!(index.get_all_posts().len(), 1);
+        assert_eq!(index.get_posts_by_tag("Rust").len(), 1);
+        let post = index.get_post_by_slug("test-article").unwrap();
+        assert_eq!(post.metadata.title, "测试文章");
+        assert!(post.html_content.is_empty());
+    }
+
+    fn post_at(file_path: &str) -> Post {
+        Post {
+            metadata: PostMetadata::new(),
+            file_path: file_path.to_string(),
+            content: String::new(),
+            html_content: String::new(),
+            ..Post::default()
+        }
+    }
+
+    #[test]
+    fn test_slug_lookup_is_exact() {
+        let mut custom = post_at("2025-11-25-renamed.md");
+        custom.metadata.slug = Some("custom-slug".to_string());
+        let index = PostIndex::from_posts(vec![
+            post_at("math-test.md"),
+            post_at("2025-11-23-test-article.md"),
+            custom,
+        ])
+        .unwrap();
+
+        assert!(index.get_post_by_slug("test").is_none());
+        assert!(index.get_post_by_slug("math").is_none());
+        assert_eq!(index.get_post_by_slug("math-test").unwrap().file_path, "math-test.md");
+        // 日期前缀被移除
+        assert!(index.get_post_by_slug("test-article").is_some());
+        assert!(index.get_post_by_slug("2025-11-23-test-article").is_none());
+        // frontmatter 中的 slug 优先于文件名
+        assert!(index.get_post_by_slug("custom-slug").is_some());
+        assert!(index.get_post_by_slug("renamed").is_none());
+    }
+
+    fn dated_post(file_path: &str, date: &str, category: &str) -> Post {
+        let mut post = post_at(file_path);
+        post.metadata.date = crate::parse_date(date);
+        post.metadata.categories = Some(vec![category.to_string()]);
+        post
+    }
+
+    #[test]
+    fn test_adjacent_posts() {
+        let index = PostIndex::from_posts(vec![
+            dated_post("first.md", "2025-01-01", "技术"),
+            dated_post("second.md", "2025-02-01", "生活"),
+            dated_post("third.md", "2025-03-01", "技术"),
+        ])
+        .unwrap();
+
+        let middle = index.get_adjacent_posts("second");
+        assert_eq!(middle.previous.unwrap().file_path, "first.md");
+        assert_eq!(middle.next.unwrap().file_path, "third.md");
+
+        // 两端只有一侧
+        let oldest = index.get_adjacent_posts("first");
+        assert!(oldest.previous.is_none());
+        assert_eq!(oldest.next.unwrap().file_path, "second.md");
+        let newest = index.get_adjacent_posts("third");
+        assert!(newest.next.is_none());
+
+        // 同一分类内跳过其他分类的文章
+        let in_category = index.get_adjacent_posts_in_category("third", "技术");
+        assert_eq!(in_category.previous.unwrap().file_path, "first.md");
+        assert!(in_category.next.is_none());
+        let in_category = index.get_adjacent_posts_in_category("first", "技术");
+        assert!(in_category.previous.is_none());
+        assert_eq!(in_category.next.unwrap().file_path, "third.md");
+        // 文章不属于该分类时没有相邻文章
+        assert!(index.get_adjacent_posts_in_category("second", "技术").next.is_none());
+
+        assert!(index.get_adjacent_posts("missing").previous.is_none());
+    }
+
+    #[test]
+    fn test_tag_and_category_listings() {
+        let mut older = dated_post("older.md", "2025-01-01", "技术");
+        older.metadata.tags = Some(vec!["Rust".to_string()]);
+        let mut newer = dated_post("newer.md", "2025-03-01", "技术");
+        newer.metadata.tags = Some(vec!["Rust".to_string(), "Dioxus".to_string()]);
+        let index = PostIndex::from_posts(vec![older, newer, dated_post("life.md", "2025-02-01", "生活")])
+            .unwrap();
+
+        // 按日期从新到旧
+        let rust_posts = index.get_posts_by_tag("Rust");
+        assert_eq!(rust_posts[0].file_path, "newer.md");
+        assert_eq!(rust_posts[1].file_path, "older.md");
+
+        assert_eq!(index.tag_counts(), vec![("Rust", 2), ("Dioxus", 1)]);
+        assert_eq!(index.category_counts(), vec![("技术", 2), ("生活", 1)]);
+    }
+
+    #[test]
+    fn test_pagination() {
+        let posts: Vec<Post> = (1..=5)
+            .map(|day| dated_post(&format!("post-{}.md", day), &format!("2025-01-0{}", day), "技术"))
+            .collect();
+        let index = PostIndex::from_posts(posts).unwrap();
+
+        let first = index.get_page(1, 2);
+        assert_eq!(first.total_pages, 3);
+        assert_eq!(first.total_posts, 5);
+        assert_eq!(first.posts[0].file_path, "post-5.md");
+        assert_eq!(first.posts.len(), 2);
+
+        let last = index.get_category_page("技术", 3, 2);
+        assert!(last.is_valid());
+        assert_eq!(last.posts.len(), 1);
+        assert_eq!(last.posts[0].file_path, "post-1.md");
+
+        assert!(!index.get_page(0, 2).is_valid());
+        assert!(!index.get_page(4, 2).is_valid());
+        assert!(index.get_page(4, 2).posts.is_empty());
+
+        // 没有文章时仍然有一页空列表
+        let empty = index.get_tag_page("missing", 1, 2);
+        assert!(empty.is_valid());
+        assert_eq!(empty.total_pages, 1);
+    }
+
+    #[test]
+    fn test_tag_cloud_weight() {
+        assert_eq!(tag_cloud_weight(1, 1, 9, 5), 1);
+        assert_eq!(tag_cloud_weight(5, 1, 9, 5), 3);
+        assert_eq!(tag_cloud_weight(9, 1, 9, 5), 5);
+        // 所有标签文章数相同时权重一致
+        assert_eq!(tag_cloud_weight(3, 3, 3, 5), 1);
+    }
+
+    #[test]
+    fn test_duplicate_slugs_are_rejected() {
+        let mut custom = post_at("other.md");
+        custom.metadata.slug = Some("test-article".to_string());
+        let err = PostIndex::from_posts(vec![post_at("2025-11-23-test-article.md"), custom])
+            .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("test-article"));
+        assert!(message.contains("other.md"));
+    }
+
+    #[test]
+    fn test_non_canonical_slugs_are_rejected() {
+        for slug in ["../../src", "My Post", "a/b", ""] {
+            let mut post = post_at("custom.md");
+            post.metadata.slug = Some(slug.to_string());
+            let message = PostIndex::from_posts(vec![post]).unwrap_err().to_string();
+            assert!(message.contains("custom.md"), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_reserved_slugs_are_rejected() {
+        for slug in ["tags", "categories"] {
+            let mut post = post_at("reserved.md");
+            post.metadata.slug = Some(slug.to_string());
+            let message = PostIndex::from_posts(vec![post]).unwrap_err().to_string();
+            assert!(message.contains("reserved.md"), "{}", message);
+            assert!(message.contains(&format!("/blog/{}", slug)), "{}", message);
+        }
+        assert!(PostIndex::from_posts(vec![post_at("2025-11-23-tags.md")]).is_err());
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_scan_sample_blogs() {
+        let index = scan_blogs_directory(Path::new(SAMPLE_BLOGS)).unwrap();
+        let post = index.get_post_by_slug("welcome-to-dioxus-blog").unwrap();
+        assert_eq!(post.file_path, "2025-11-23-welcome-to-dioxus-blog.md");
+        assert!(index.get_post_by_slug("math-test").is_some());
+        assert_eq!(index.sorted_by_date.len(), index.posts.len());
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_scan_nested_directories_and_bundles() {
+        let dir = std::env::temp_dir().join(format!("blog-content-bundles-{}", std::process::id()));
+        let write = |path: &str, content: &str| {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write("2025/11/2025-11-20-nested.md", "---\ntitle: 子目录\n---\n正文\n");
+        write(
+            "2025/2025-11-21-my-post/index.md",
+            "---\ntitle: 页面包\ncover_image: cover.png\n---\n![猫](./images/cat.png) ![缺失](dog.png) ![站点](/assets/header.svg)\n",
+        );
+        write("2025/2025-11-21-my-post/cover.png", "png");
+        write("2025/2025-11-21-my-post/images/cat.png", "png");
+        write("2025/2025-11-21-my-post/notes.md", "不是单独的文章");
+        write("2025/2025-11-21-my-post/2025-11-22-child/index.md", "---\ntitle: 嵌套的页面包\n---\n![图](child.png)\n");
+        write("2025/2025-11-21-my-post/2025-11-22-child/child.png", "png");
+
+        let files: Vec<String> = post_files(&dir).into_iter().map(|(_, relative)| relative).collect();
+        assert_eq!(
+            files,
+            vec![
+                "2025/11/2025-11-20-nested.md",
+                "2025/2025-11-21-my-post/2025-11-22-child/index.md",
+                "2025/2025-11-21-my-post/index.md",
+            ]
+        );
+
+        let index = scan_blogs_directory(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(index.posts.len(), 3);
+
+        let nested = index.get_post_by_slug("nested").unwrap();
+        assert_eq!(nested.file_path, "2025/11/2025-11-20-nested.md");
+        assert_eq!(nested.metadata.date, chrono::NaiveDate::from_ymd_opt(2025, 11, 20));
+        assert!(nested.assets.is_empty());
+
+        let bundle = index.get_post_by_slug("my-post").unwrap();
+        assert_eq!(bundle.metadata.date, chrono::NaiveDate::from_ymd_opt(2025, 11, 21));
+        assert_eq!(bundle.assets, vec!["cover.png", "images/cat.png"]);
+        assert_eq!(bundle.metadata.cover_image.as_deref(), Some("/assets/posts/my-post/cover.png"));
+        assert!(bundle.html_content.contains(r#"<img src="/assets/posts/my-post/images/cat.png" alt="猫">"#));
+        assert!(bundle.html_content.contains(r#"<img src="dog.png" alt="缺失">"#));
+        assert!(bundle.html_content.contains(r#"<img src="/assets/header.svg" alt="站点">"#));
+
+        // 嵌套的页面包只拥有自己目录中的文件
+        let child = index.get_post_by_slug("child").unwrap();
+        assert_eq!(child.assets, vec!["child.png"]);
+        assert!(child.html_content.contains(r#"<img src="/assets/posts/child/child.png" alt="图">"#));
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_build_and_runtime_indexes_match() {
+        // build.rs 写出的 JSON 与运行时反序列化得到的索引必须一致
+        let built = scan_blogs_directory(Path::new(SAMPLE_BLOGS)).unwrap();
+        let loaded = PostIndex::from_json(&built.to_json().unwrap()).unwrap();
+
+        let mut expected = built.clone();
+        for post in &mut expected.posts {
+            post.content.clear();
+            post.content_line = 0;
+            post.assets.clear();
+            post.html_content.clear();
+            // 仅构建时使用的字段不写入索引
+            post.metadata.bibliography = None;
+            post.metadata.citation_style = Default::default();
+            post.metadata.trusted = false;
+        }
+        assert_eq!(loaded, expected);
+    }
+}
+
@@ -0,0 +1,77 @@
+//! 博客内容模型
+//!
+//! build.rs 与应用共用同一套 Frontmatter 解析、slug 生成、日期解析和草稿规则，
+//! 保证构建时生成的索引与运行时看到的文章完全一致。
+//!
+//! Markdown/YAML 解析与渲染位于 `markdown` feature 之后，只在构建时启用，
+//! 运行时（WASM）只需要反序列化预先生成的索引。
+
+pub mod metadata;
+pub mod post;
+#[cfg(feature = "markdown")]
+pub mod frontmatter;
+#[cfg(feature = "markdown")]
+pub mod markdown;
+#[cfg(feature = "markdown")]
+pub mod highlight;
+#[cfg(feature = "markdown")]
+pub mod math;
+#[cfg(feature = "markdown")]
+pub mod citation;
+#[cfg(feature = "markdown")]
+pub mod sanitize;
+#[cfg(feature = "markdown")]
+pub mod links;
+#[cfg(feature = "markdown")]
+pub mod lint;
+pub mod index;
+pub mod site;
+pub mod feed;
+pub mod sitemap;
+pub mod prerender;
+pub mod seo;
+pub mod og_card;
+pub mod text;
+pub mod search;
+
+pub use metadata::{
+    date_from_filename, is_bundle_index, parse_date, parse_datetime, slug_from_filename, slugify, CitationStyle, PostMetadata,
+};
+pub use post::{Post, TocEntry};
+#[cfg(feature = "markdown")]
+pub use frontmatter::{
+    frontmatter_fields, parse_frontmatter, FrontmatterError, FrontmatterField, FrontmatterErrorKind,
+    FrontmatterFormat,
+};
+#[cfg(feature = "markdown")]
+pub use markdown::{
+    heading_id, markdown_word_count, parse_markdown_file, parse_markdown_content, process_markdown_file,
+    process_post_file, render_markdown,
+    render_markdown_document, render_markdown_with_citations, render_markdown_with_toc,
+    RenderedMarkdown,
+};
+pub use index::{tag_cloud_weight, AdjacentPosts, Page, PostIndex};
+pub use site::{decode_path_segment, encode_path_segment, SiteConfig};
+pub use feed::{generate_feeds, FeedFile};
+pub use sitemap::{generate_robots, generate_sitemap, sitemap_entries, SitemapEntry};
+pub use prerender::{asset_head_html, inject_head, inject_into_shell, output_file, prerender_paths};
+pub use seo::{MetaTag, PageMeta};
+pub use og_card::og_card_svg;
+pub use text::{count_words, WordCount};
+pub use search::{SearchDoc, SearchHit, SearchIndex, TextPart};
+#[cfg(feature = "og-image")]
+pub use og_card::OgCardRenderer;
+#[cfg(feature = "markdown")]
+pub use index::{post_files, scan_blogs_directory};
+#[cfg(feature = "markdown")]
+pub use highlight::{highlight_code, highlight_css};
+#[cfg(feature = "markdown")]
+pub use citation::{Bibliography, Citations, Reference};
+#[cfg(feature = "markdown")]
+pub use math::{latex_to_mathml, render_math, Equation, Equations, MathStyle};
+#[cfg(feature = "markdown")]
+pub use sanitize::sanitize_html;
+#[cfg(feature = "markdown")]
+pub use links::{check_links, BrokenLink};
+#[cfg(feature = "markdown")]
+pub use lint::{lint_directory, Diagnostic, LintReport, Severity};
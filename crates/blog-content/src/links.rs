@@ -0,0 +1,269 @@
+//! 站内链接检查
+//!
+//! 把文章中的每个链接和图片按浏览器的规则解析为站内路径，再与文章索引生成的路由、
+//! 渲染结果中的锚点（标题、公式、参考文献、脚注）以及 `public/`、仓库根目录下的文件对照，
+//! 找出重命名 slug 或标题后失效的链接。
+
+use crate::markdown::markdown_options;
+use crate::site::{is_external, post_path, resolve};
+use crate::{decode_path_segment, is_bundle_index, prerender_paths, Post, PostIndex, SiteConfig};
+use pulldown_cmark::{Event, Parser, Tag};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+/// 一个失效的链接
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLink {
+    /// 相对于 blogs 目录的源文件路径
+    pub file: String,
+    /// 链接所在行（从 1 开始）
+    pub line: usize,
+    /// 链接原文
+    pub url: String,
+    /// 失效原因
+    pub reason: String,
+}
+
+impl fmt::Display for BrokenLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: 链接失效 {}：{}", self.file, self.line, self.url, self.reason)
+    }
+}
+
+/// 检查所有文章中的站内链接
+///
+/// `root` 为站点根目录，不属于任何路由的路径按文件查找：先找 `root/public`，`/assets/` 下的路径再找 `root/assets`
+pub fn check_links(index: &PostIndex, site: &SiteConfig, root: &Path) -> Vec<BrokenLink> {
+    // 路由 → 页面中的锚点；只有文章页知道有哪些锚点
+    let mut pages: HashMap<String, Option<HashSet<String>>> = prerender_paths(index, site)
+        .into_iter()
+        .chain(["/search".to_string()])
+        .map(|path| (normalize_path(&path), None))
+        .collect();
+    for post in &index.posts {
+        pages.insert(normalize_path(&post_path(&post.slug())), Some(html_ids(&post.html_content)));
+    }
+
+    let mut broken = Vec::new();
+    for post in &index.posts {
+        let bundle = is_bundle_index(&post.file_path);
+        for (url, line, image) in post_links(post) {
+            // 页面包中的相对图片在渲染时已改写为资源地址，对照资源文件检查
+            let reason = match bundle_resource(&url).filter(|_| bundle && image) {
+                Some(file) if post.assets.contains(&file) => None,
+                Some(file) => Some(format!("页面包中没有文件 {}", file)),
+                None => check_link(&url, post, &pages, root),
+            };
+            if let Some(reason) = reason {
+                broken.push(BrokenLink { file: post.file_path.clone(), line, url, reason });
+            }
+        }
+    }
+    broken
+}
+
+/// 正文中的链接和图片地址、所在行以及是否为图片
+fn post_links(post: &Post) -> Vec<(String, usize, bool)> {
+    let content = &post.content;
+    let line = |offset: usize| post.content_line.max(1) + content[..offset].matches('\n').count();
+    Parser::new_ext(content, markdown_options())
+        .into_offset_iter()
+        .filter_map(|(event, range)| match event {
+            Event::Start(Tag::Link { dest_url, .. }) => Some((dest_url.to_string(), line(range.start), false)),
+            Event::Start(Tag::Image { dest_url, .. }) => Some((dest_url.to_string(), line(range.start), true)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 检查单个链接，返回失效原因
+fn check_link(
+    url: &str,
+    post: &Post,
+    pages: &HashMap<String, Option<HashSet<String>>>,
+    root: &Path,
+) -> Option<String> {
+    if url.is_empty() || is_external(url) {
+        return None;
+    }
+    let (path, fragment) = url.split_once('#').unwrap_or((url, ""));
+    let path = path.split('?').next().unwrap_or("");
+    let fragment = decode_path_segment(fragment);
+
+    // 仅有锚点：链接到本文
+    if path.is_empty() {
+        let current = normalize_path(&post_path(&post.slug()));
+        return match pages.get(&current) {
+            Some(Some(ids)) if !fragment.is_empty() && !ids.contains(&fragment) => {
+                Some(format!("本文中没有锚点 #{}", fragment))
+            }
+            _ => None,
+        };
+    }
+
+    let target = normalize_path(&resolve(&post_path(&post.slug()), path));
+    match pages.get(&target) {
+        Some(Some(ids)) if !fragment.is_empty() && !ids.contains(&fragment) => {
+            Some(format!("{} 中没有锚点 #{}", target, fragment))
+        }
+        Some(_) => None,
+        None if static_file_exists(&target, root) => None,
+        None if target.ends_with(".md") => {
+            Some("找不到对应的页面或文件（链接文章请使用 /blog/<slug>）".to_string())
+        }
+        None => Some("找不到对应的页面或文件".to_string()),
+    }
+}
+
+/// 解码路径段并去掉末尾的 `/`，使 `/blog/tag/%E5%8D%9A` 与 `/blog/tag/博/` 相同
+fn normalize_path(path: &str) -> String {
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(decode_path_segment)
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+/// 渲染结果中所有元素的 `id`
+fn html_ids(html: &str) -> HashSet<String> {
+    html.split(" id=\"")
+        .skip(1)
+        .filter_map(|rest| rest.split('"').next())
+        .map(|id| id.replace("&amp;", "&").replace("&quot;", "\"").replace("&lt;", "<").replace("&gt;", ">"))
+        .collect()
+}
+
+/// 页面包中的资源：不带协议、不以 `/` 或 `#` 开头且不跳出文章目录的相对路径，
+/// 返回去掉 `./`、查询参数和锚点并解码后的文件路径
+pub(crate) fn bundle_resource(url: &str) -> Option<String> {
+    if url.is_empty() || is_external(url) || url.starts_with(['/', '#', '?']) {
+        return None;
+    }
+    let path = url.split(['?', '#']).next().unwrap_or("");
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|s| !s.is_empty() && *s != ".")
+        .map(decode_path_segment)
+        .collect();
+    if segments.is_empty() || segments.iter().any(|s| s == "..") {
+        return None;
+    }
+    Some(segments.join("/"))
+}
+
+/// 站点根路径对应的文件是否存在
+///
+/// 只有 `public/` 和 `assets/` 中的文件会发布到站点，仓库根目录下的其他文件（如 `/Cargo.toml`）不算
+pub(crate) fn static_file_exists(path: &str, root: &Path) -> bool {
+    let path = decode_path_segment(path.trim_start_matches('/'));
+    if path.is_empty() || path.split('/').any(|segment| segment == "..") {
+        return false;
+    }
+    root.join("public").join(&path).is_file() || (path.starts_with("assets/") && root.join(&path).is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_markdown_content, render_markdown_document};
+
+    fn post(file: &str, markdown: &str) -> Post {
+        let mut post = parse_markdown_content(markdown).unwrap();
+        post.file_path = file.to_string();
+        post.html_content = render_markdown_document(&post.content).html;
+        post
+    }
+
+    fn check(posts: Vec<Post>) -> Vec<String> {
+        let index = PostIndex::from_posts(posts).unwrap();
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        check_links(&index, &SiteConfig::default(), &root)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(resolve("/blog/a", "b"), "/blog/b");
+        assert_eq!(resolve("/blog/a", "../assets/x.png"), "/assets/x.png");
+        assert_eq!(resolve("/blog/a", "./b/c"), "/blog/b/c");
+        assert_eq!(resolve("/blog/a", "/blog/tags"), "/blog/tags");
+        assert_eq!(normalize_path("/blog/tag/%E5%8D%9A%E5%AE%A2/"), "/blog/tag/博客");
+        assert_eq!(normalize_path("/"), "/");
+    }
+
+    #[test]
+    fn test_valid_links() {
+        let a = post(
+            "2025-11-23-a.md",
+            "---\ntitle: A\ndate: 2025-11-23\ntags: [博客]\n---\n# 第一节\n\n[本节](#第一节) [B](/blog/b#方法) [相对](b) [标签](/blog/tag/博客) \
+             [编码](/blog/tag/%E5%8D%9A%E5%AE%A2) [首页](/) [搜索](/search?q=x) [外链](https://example.com/#x) \
+             [邮件](mailto:a@example.com) [图标](/assets/favicon.ico) ![图](../assets/header.svg)\n",
+        );
+        let b = post("2025-11-24-b.md", "---\ntitle: B\ndate: 2025-11-24\n---\n## 方法\n\n正文[^1]\n\n[^1]: 脚注\n");
+        assert!(check(vec![a, b]).is_empty());
+    }
+
+    #[test]
+    fn test_broken_links() {
+        let a = post(
+            "a.md",
+            "---\ntitle: A\ndate: 2025-11-23\n---\n# 第一节\n\n[锚点](#第二节)\n\n[改名](/blog/old-slug)\n[标题](/blog/b#旧标题) [文件](/assets/missing.png)\n\n[源文件](b.md)\n",
+        );
+        let b = post("b.md", "---\ntitle: B\ndate: 2025-11-24\n---\n## 方法\n");
+        assert_eq!(
+            check(vec![a, b]),
+            vec![
+                "a.md:7: 链接失效 #第二节：本文中没有锚点 #第二节",
+                "a.md:9: 链接失效 /blog/old-slug：找不到对应的页面或文件",
+                "a.md:10: 链接失效 /blog/b#旧标题：/blog/b 中没有锚点 #旧标题",
+                "a.md:10: 链接失效 /assets/missing.png：找不到对应的页面或文件",
+                "a.md:12: 链接失效 b.md：找不到对应的页面或文件（链接文章请使用 /blog/<slug>）",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unpublished_files_are_broken() {
+        let a = post(
+            "a.md",
+            "---\ntitle: A\ndate: 2025-11-23\n---\n[清单](/Cargo.toml) [构建](/build.rs) [源文件](/blogs/2025-11-23-welcome-to-dioxus-blog.md) [跳出](/assets/../site.yaml)\n",
+        );
+        let broken = check(vec![a]);
+        assert_eq!(broken.len(), 4, "{:#?}", broken);
+        assert!(broken[0].ends_with("/Cargo.toml：找不到对应的页面或文件"));
+    }
+
+    #[test]
+    fn test_generated_anchors() {
+        let a = post(
+            "a.md",
+            "---\ntitle: A\ndate: 2025-11-23\n---\n$$x \\label{eq:x}$$\n\n[公式](#eq-1) [缺失](#eq-2)\n",
+        );
+        assert_eq!(check(vec![a]), vec!["a.md:7: 链接失效 #eq-2：本文中没有锚点 #eq-2"]);
+    }
+
+    #[test]
+    fn test_bundle_images() {
+        let mut a = post(
+            "2025-11-23-a/index.md",
+            "---\ntitle: A\ndate: 2025-11-23\n---\n![猫](./images/cat.png) ![缺失](dog.png)\n",
+        );
+        a.assets = vec!["images/cat.png".to_string()];
+        assert_eq!(check(vec![a]), vec!["2025-11-23-a/index.md:5: 链接失效 dog.png：页面包中没有文件 dog.png"]);
+        assert_eq!(bundle_resource("./images/%E7%8C%AB.png?v=1"), Some("images/猫.png".to_string()));
+        assert_eq!(bundle_resource("../x.png"), None);
+        assert_eq!(bundle_resource("/assets/x.png"), None);
+    }
+
+    #[test]
+    fn test_sample_blogs_have_no_broken_links() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let index = crate::scan_blogs_directory(&root.join("blogs")).unwrap();
+        let broken = check_links(&index, &SiteConfig::default(), &root);
+        assert!(broken.is_empty(), "{:#?}", broken);
+    }
+}
@@ -0,0 +1,447 @@
+//! 文章检查：`blog lint` 使用
+//!
+//! 构建时的解析对 Frontmatter 很宽容（无法解析的日期变成 `None`、未知字段被忽略、
+//! 缺少标题时使用"未命名文章"），这里按更严格的规则逐篇检查，
+//! 报告带文件和行号的诊断，避免有问题的文章被发布。
+
+use crate::frontmatter::{frontmatter_fields, parse_frontmatter, FrontmatterField};
+use crate::links::{bundle_resource, static_file_exists};
+use crate::site::{is_external, is_safe_path_segment, RESERVED_SLUGS};
+use crate::{
+    check_links, date_from_filename, is_bundle_index, parse_date, parse_datetime, post_files, process_post_file,
+    scan_blogs_directory, slugify, PostMetadata, SiteConfig,
+};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Frontmatter 中可以使用的字段，与 [`PostMetadata`] 的字段保持一致
+const KNOWN_FIELDS: &[&str] = &[
+    "title",
+    "date",
+    "author",
+    "tags",
+    "categories",
+    "summary",
+    "cover_image",
+    "slug",
+    "draft",
+    "updated",
+    "layout",
+    "bibliography",
+    "citation_style",
+    "trusted",
+];
+
+/// 诊断级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// 必须修复，`blog lint` 以非零状态退出
+    Error,
+    /// 渲染时发现的问题，只提示
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Error => "错误",
+            Self::Warning => "警告",
+        })
+    }
+}
+
+/// 一条检查结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// 文件路径
+    pub file: String,
+    /// 行号（从 1 开始），与整篇文章有关时为 `None`
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}: {}", self.file, line, self.severity, self.message),
+            None => write!(f, "{}: {}: {}", self.file, self.severity, self.message),
+        }
+    }
+}
+
+/// 检查整个目录的结果
+#[derive(Debug, Clone, Default)]
+pub struct LintReport {
+    /// 检查过的文章数
+    pub files: usize,
+    /// 按文件顺序排列的诊断
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl LintReport {
+    /// 错误数量
+    pub fn errors(&self) -> usize {
+        self.diagnostics.iter().filter(|d| d.severity == Severity::Error).count()
+    }
+
+    /// 警告数量
+    pub fn warnings(&self) -> usize {
+        self.diagnostics.len() - self.errors()
+    }
+}
+
+/// 检查 `blogs_dir` 下的所有文章（含草稿）
+///
+/// `root` 为站点根目录，站内链接和图片按 [`check_links`] 的规则解析；
+/// `site` 中配置了 `tags` / `categories` 时，只允许使用其中列出的值
+pub fn lint_directory(blogs_dir: &Path, root: &Path, site: &SiteConfig) -> LintReport {
+    let mut report = LintReport::default();
+    let mut slugs: HashMap<String, String> = HashMap::new();
+
+    for (path, relative_path) in post_files(blogs_dir) {
+        let path = path.as_path();
+        let file = path.to_string_lossy().replace('\\', "/");
+        report.files += 1;
+
+        let mut lint = FileLint { file: &file, diagnostics: Vec::new() };
+        let Some((metadata, fields)) = lint.check_file(path, &relative_path, root, site) else {
+            report.diagnostics.extend(lint.diagnostics);
+            continue;
+        };
+
+        // slug 必须唯一，否则后发布的文章会覆盖先前的路由
+        let slug = metadata.get_slug(&relative_path);
+        let line = field(&fields, "slug").map(|f| f.line);
+        if RESERVED_SLUGS.contains(&slug.as_str()) {
+            lint.error(line, format!("slug `{}` 与 /blog/{} 页面冲突", slug, slug));
+        }
+        match slugs.get(&slug) {
+            Some(other) => lint.error(line, format!("slug `{}` 与 {} 重复", slug, other)),
+            None => {
+                slugs.insert(slug, file.clone());
+            }
+        }
+        report.diagnostics.extend(lint.diagnostics);
+    }
+
+    // 站内链接与锚点（slug 或标签、分类名有误时无法生成索引，已在上面报告）
+    if let Ok(index) = scan_blogs_directory(blogs_dir) {
+        for link in check_links(&index, site, root) {
+            report.diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                file: blogs_dir.join(&link.file).to_string_lossy().replace('\\', "/"),
+                line: Some(link.line),
+                message: format!("链接失效 {}：{}", link.url, link.reason),
+            });
+        }
+    }
+
+    report
+}
+
+/// 单个文件的检查状态
+struct FileLint<'a> {
+    file: &'a str,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl FileLint<'_> {
+    fn push(&mut self, severity: Severity, line: Option<usize>, message: String) {
+        self.diagnostics.push(Diagnostic { severity, file: self.file.to_string(), line, message });
+    }
+
+    fn error(&mut self, line: Option<usize>, message: String) {
+        self.push(Severity::Error, line, message);
+    }
+
+    /// 检查 Frontmatter 与正文，Frontmatter 无法解析时返回 `None`
+    fn check_file(
+        &mut self,
+        path: &Path,
+        relative_path: &str,
+        root: &Path,
+        site: &SiteConfig,
+    ) -> Option<(PostMetadata, Vec<FrontmatterField>)> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                self.error(None, format!("无法读取文件: {}", e));
+                return None;
+            }
+        };
+        let parsed = frontmatter_fields(&content, self.file)
+            .and_then(|fields| Ok((fields, parse_frontmatter(&content, self.file)?)));
+        let (fields, (metadata, _)) = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                self.error(Some(e.line), e.kind.to_string());
+                return None;
+            }
+        };
+        let post_dir = path.parent().unwrap_or(Path::new(""));
+        self.check_fields(&fields, &metadata, relative_path, root, post_dir, site);
+
+        // 渲染时发现的问题（公式、引用等）
+        if let Ok(post) = process_post_file(path, relative_path) {
+            for warning in post.warnings {
+                self.push(Severity::Warning, None, warning);
+            }
+        }
+
+        Some((metadata, fields))
+    }
+
+    fn check_fields(
+        &mut self,
+        fields: &[FrontmatterField],
+        metadata: &PostMetadata,
+        relative_path: &str,
+        root: &Path,
+        post_dir: &Path,
+        site: &SiteConfig,
+    ) {
+        for f in fields {
+            if !KNOWN_FIELDS.contains(&f.key.as_str()) {
+                self.error(Some(f.line), format!("未知的 Frontmatter 字段 `{}`", f.key));
+            }
+        }
+
+        // 必填字段
+        match field(fields, "title") {
+            None => self.error(Some(1), "缺少 title".to_string()),
+            Some(f) if metadata.title.trim().is_empty() => self.error(Some(f.line), "title 为空".to_string()),
+            Some(_) => {}
+        }
+        match field(fields, "date") {
+            Some(f) if f.value.as_str().and_then(parse_date).is_none() => self.error(
+                Some(f.line),
+                format!("无法解析的日期 {}，应为 YYYY-MM-DD", f.value),
+            ),
+            None if date_from_filename(relative_path).is_none() => self.error(
+                Some(1),
+                "缺少发布日期：请设置 date 或在文件名前加 YYYY-MM-DD-".to_string(),
+            ),
+            _ => {}
+        }
+        if let Some(f) = field(fields, "updated").filter(|f| !f.value.is_null()) {
+            if f.value.as_str().and_then(parse_datetime).is_none() {
+                self.error(
+                    Some(f.line),
+                    format!("无法解析的更新时间 {}，应为 YYYY-MM-DD 或 YYYY-MM-DD HH:MM:SS", f.value),
+                );
+            }
+        }
+
+        // 标签和分类必须在 site.yaml 中登记
+        let taxonomies = [
+            ("tags", "标签", &metadata.tags, &site.tags),
+            ("categories", "分类", &metadata.categories, &site.categories),
+        ];
+        for (key, name, values, known) in taxonomies {
+            let line = field(fields, key).map(|f| f.line);
+            for value in values.iter().flatten() {
+                if !is_safe_path_segment(value) {
+                    self.error(line, format!("{} `{}` 不能为空、. 或 ..，也不能包含 / 或 \\", name, value));
+                } else if !known.is_empty() && !known.contains(value) {
+                    self.error(line, format!("{} `{}` 未在 site.yaml 的 {} 中登记", name, value, key));
+                }
+            }
+        }
+
+        if let Some(slug) = &metadata.slug {
+            if slugify(slug) != *slug || slug.is_empty() {
+                let line = field(fields, "slug").map(|f| f.line);
+                self.error(line, format!("slug `{}` 不是规范形式，应为 `{}`", slug, slugify(slug)));
+            }
+        }
+
+        if let Some(cover) = metadata.cover_image.as_deref().filter(|cover| !is_external(cover)) {
+            // 封面会出现在列表页等多个页面上，只能使用站点根路径或页面包中的文件
+            let line = field(fields, "cover_image").map(|f| f.line);
+            let bundled = is_bundle_index(relative_path).then(|| bundle_resource(cover)).flatten();
+            let exists = match &bundled {
+                Some(file) => post_dir.join(file).is_file(),
+                None => static_file_exists(cover.split(['?', '#']).next().unwrap_or(""), root),
+            };
+            if bundled.is_none() && !cover.starts_with('/') {
+                self.error(line, format!("封面图片应使用以 / 开头的路径: {}", cover));
+            } else if !exists {
+                self.error(line, format!("封面图片不存在: {}", cover));
+            }
+        }
+    }
+}
+
+fn field<'a>(fields: &'a [FrontmatterField], key: &str) -> Option<&'a FrontmatterField> {
+    fields.iter().find(|f| f.key == key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 在临时目录中写入文章并检查，返回不含文件名的诊断
+    fn lint(files: &[(&str, &str)], site: &SiteConfig) -> Vec<String> {
+        let root = std::env::temp_dir().join(format!(
+            "blog-content-lint-{}-{}",
+            std::process::id(),
+            files[0].0.replace(['/', '.'], "-")
+        ));
+        let blogs = root.join("blogs");
+        fs::create_dir_all(root.join("public/images")).unwrap();
+        fs::create_dir_all(&blogs).unwrap();
+        fs::write(root.join("public/images/a.png"), b"").unwrap();
+        for (name, content) in files {
+            let path = blogs.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let report = lint_directory(&blogs, &root, site);
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(report.files, files.iter().filter(|(name, _)| name.ends_with(".md")).count());
+        let prefix = format!("{}/", blogs.to_string_lossy().replace('\\', "/"));
+        report.diagnostics.iter().map(|d| d.to_string().replace(&prefix, "")).collect()
+    }
+
+    #[test]
+    fn test_valid_post() {
+        let post = "---\ntitle: 标题\ndate: 2025-11-23\ntags: [Rust]\ncover_image: /images/a.png\n---\n![外链](https://example.com/x.png)\n[首页](/)\n";
+        assert!(lint(&[("valid.md", post)], &SiteConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_schema_errors() {
+        let post = "---\ntitel: 标题\ndate: 2025/13/01\nupdated: yesterday\nslug: My Post\n---\n正文\n";
+        assert_eq!(
+            lint(&[("schema.md", post)], &SiteConfig::default()),
+            vec![
+                "schema.md:2: 错误: 未知的 Frontmatter 字段 `titel`",
+                "schema.md:1: 错误: 缺少 title",
+                "schema.md:3: 错误: 无法解析的日期 \"2025/13/01\"，应为 YYYY-MM-DD",
+                "schema.md:4: 错误: 无法解析的更新时间 \"yesterday\"，应为 YYYY-MM-DD 或 YYYY-MM-DD HH:MM:SS",
+                "schema.md:5: 错误: slug `My Post` 不是规范形式，应为 `my-post`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_date_and_invalid_frontmatter() {
+        let diagnostics = lint(
+            &[("nodate.md", "---\ntitle: a\n---\n"), ("zbroken.md", "---\ntitle: a\ndraft: maybe\n---\n")],
+            &SiteConfig::default(),
+        );
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0], "nodate.md:1: 错误: 缺少发布日期：请设置 date 或在文件名前加 YYYY-MM-DD-");
+        assert!(diagnostics[1].starts_with("zbroken.md:3: 错误: YAML Frontmatter 解析失败"), "{}", diagnostics[1]);
+
+        // 文件名中的日期可以代替 date
+        assert!(lint(&[("2025-11-23-a.md", "---\ntitle: a\n---\n")], &SiteConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_known_tags_and_categories() {
+        let site = SiteConfig {
+            tags: vec!["Rust".to_string()],
+            categories: vec!["技术".to_string()],
+            ..SiteConfig::default()
+        };
+        let post = "---\ntitle: a\ndate: 2025-11-23\ntags: [Rust, Rsut]\ncategories: [随笔]\n---\n";
+        assert_eq!(
+            lint(&[("taxonomy.md", post)], &site),
+            vec![
+                "taxonomy.md:4: 错误: 标签 `Rsut` 未在 site.yaml 的 tags 中登记",
+                "taxonomy.md:5: 错误: 分类 `随笔` 未在 site.yaml 的 categories 中登记",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_slugs_and_missing_images() {
+        let first = "---\ntitle: a\ndate: 2025-11-23\ncover_image: images/a.png\n---\n";
+        let second = "---\ntitle: b\ndate: 2025-11-23\nslug: hello\ncover_image: /images/missing.png\n---\n";
+        assert_eq!(
+            lint(&[("hello.md", first), ("other.md", second)], &SiteConfig::default()),
+            vec![
+                "hello.md:4: 错误: 封面图片应使用以 / 开头的路径: images/a.png",
+                "other.md:5: 错误: 封面图片不存在: /images/missing.png",
+                "other.md:4: 错误: slug `hello` 与 hello.md 重复",
+            ]
+        );
+
+        // 正文中的图片由链接检查报告
+        let post = "---\ntitle: a\ndate: 2025-11-23\n---\n\n正文\n\n![图](/images/missing%20image.png)\n";
+        assert_eq!(
+            lint(&[("image.md", post)], &SiteConfig::default()),
+            vec!["image.md:8: 错误: 链接失效 /images/missing%20image.png：找不到对应的页面或文件"]
+        );
+    }
+
+    #[test]
+    fn test_bundle_cover_and_images() {
+        let post = "---\ntitle: a\ncover_image: cover.png\n---\n![图](./cat.png) ![缺失](dog.png)\n";
+        assert_eq!(
+            lint(
+                &[("2025-11-23-bundle/index.md", post), ("2025-11-23-bundle/cover.png", ""), ("2025-11-23-bundle/cat.png", "")],
+                &SiteConfig::default(),
+            ),
+            vec!["2025-11-23-bundle/index.md:5: 错误: 链接失效 dog.png：页面包中没有文件 dog.png"]
+        );
+
+        let post = "---\ntitle: a\ncover_image: cover.png\n---\n";
+        assert_eq!(
+            lint(&[("2025-11-23-missing/index.md", post)], &SiteConfig::default()),
+            vec!["2025-11-23-missing/index.md:3: 错误: 封面图片不存在: cover.png"]
+        );
+    }
+
+    #[test]
+    fn test_unsafe_taxonomy_names() {
+        let post = "---\ntitle: a\ndate: 2025-11-23\ntags: [\"../x\", \"a/b\"]\ncategories: [\"..\"]\n---\n";
+        assert_eq!(
+            lint(&[("unsafe.md", post)], &SiteConfig::default()),
+            vec![
+                "unsafe.md:4: 错误: 标签 `../x` 不能为空、. 或 ..，也不能包含 / 或 \\",
+                "unsafe.md:4: 错误: 标签 `a/b` 不能为空、. 或 ..，也不能包含 / 或 \\",
+                "unsafe.md:5: 错误: 分类 `..` 不能为空、. 或 ..，也不能包含 / 或 \\",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reserved_slug() {
+        assert_eq!(
+            lint(&[("2025-11-23-tags.md", "---\ntitle: a\n---\n")], &SiteConfig::default()),
+            vec!["2025-11-23-tags.md: 错误: slug `tags` 与 /blog/tags 页面冲突"]
+        );
+    }
+
+    #[test]
+    fn test_broken_links() {
+        let post = "---\ntitle: a\ndate: 2025-11-23\n---\n# 标题\n\n[旧文章](/blog/old) [本节](#标题)\n";
+        assert_eq!(
+            lint(&[("links.md", post)], &SiteConfig::default()),
+            vec!["links.md:7: 错误: 链接失效 /blog/old：找不到对应的页面或文件"]
+        );
+    }
+
+    #[test]
+    fn test_render_warnings() {
+        let post = "---\ntitle: a\ndate: 2025-11-23\n---\n$$x \\label{a}$$\n\n$$y \\label{a}$$\n";
+        let report = lint(&[("warn.md", post)], &SiteConfig::default());
+        assert_eq!(report, vec!["warn.md: 警告: 公式标签重复: a"]);
+    }
+
+    #[test]
+    fn test_sample_blogs_are_clean() {
+        let root = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../.."));
+        let yaml = fs::read_to_string(root.join("site.yaml")).unwrap();
+        let site = SiteConfig::from_yaml(&yaml).unwrap();
+        let report = lint_directory(&root.join("blogs"), root, &site);
+        assert!(report.files >= 3);
+        assert!(report.diagnostics.is_empty(), "{:#?}", report.diagnostics);
+    }
+}
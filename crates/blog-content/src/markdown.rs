@@ -0,0 +1,777 @@
+use crate::feed::escape_xml;
+use crate::text::{count_words, html_to_text, truncate_chars, WordCount};
+use crate::links::bundle_resource;
+use crate::metadata::is_bundle_index;
+use crate::site::post_asset_path;
+use crate::{slugify, Post, TocEntry};
+use anyhow::{Context, Result};
+use crate::citation::{Bibliography, Citations};
+use crate::frontmatter::parse_frontmatter;
+use crate::highlight::highlight_code;
+use crate::sanitize::{is_layout_id, sanitize_html};
+use crate::math::{parse_reference, reference_text, render_math_with, Equations, MathStyle};
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// 解析 Markdown 文件，分离 Frontmatter 和正文
+pub fn parse_markdown_file(path: &Path) -> Result<Post> {
+    // 读取文件内容
+    let content =
+        fs::read_to_string(path).with_context(|| format!("无法读取文件: {}", path.display()))?;
+
+    // 分离 Frontmatter 和正文
+    let file_path = path.to_string_lossy().replace('\\', "/");
+    let (metadata, markdown_content) = parse_frontmatter(&content, &file_path)?;
+    let content_line = content[..content.len() - markdown_content.len()].matches('\n').count() + 1;
+
+    Ok(Post {
+        metadata,
+        file_path,
+        content: markdown_content,
+        content_line,
+        html_content: String::new(), // 稍后渲染
+        ..Post::default()
+    })
+}
+
+/// 解析 Markdown 内容（字符串），分离 Frontmatter 和正文
+pub fn parse_markdown_content(content: &str) -> Result<Post> {
+    // 分离 Frontmatter 和正文
+    let (metadata, markdown_content) = parse_frontmatter(content, "<input>")?;
+    let content_line = content[..content.len() - markdown_content.len()].matches('\n').count() + 1;
+
+    Ok(Post {
+        metadata,
+        file_path: String::new(),
+        content: markdown_content,
+        content_line,
+        html_content: String::new(), // 稍后渲染
+        ..Post::default()
+    })
+}
+
+/// 启用的 Markdown 扩展：除元数据块以外全部启用
+pub(crate) fn markdown_options() -> Options {
+    // Frontmatter 已由 frontmatter.rs 解析并去掉，正文开头的 `---` 块不能再被当作元数据丢弃
+    Options::all()
+        - Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+        - Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS
+}
+
+/// 数学公式引用 `\eqref{…}` / `\ref{…}` 对应的 HTML 链接
+fn reference_html(command: &str, label: &str, equations: &mut Equations) -> String {
+    let equation = equations.check_reference(label).cloned();
+    let text = escape_xml(&reference_text(command, equation.as_ref()));
+    match equation {
+        Some(equation) => format!("<a class=\"eqref\" href=\"#{}\">{}</a>", equation.id, text),
+        None => format!("<span class=\"eqref eqref-missing\">{}</span>", text),
+    }
+}
+
+/// 把正文中的 `\eqref{…}` / `\ref{…}` 替换为公式链接
+fn link_references(text: &str, equations: &mut Equations) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+    while let Some(start) = [rest.find("\\eqref{"), rest.find("\\ref{")].into_iter().flatten().min() {
+        let command = if rest[start..].starts_with("\\eqref{") { "eqref" } else { "ref" };
+        let label_start = start + command.len() + 2;
+        let Some(len) = rest[label_start..].find('}') else {
+            break;
+        };
+        html.push_str(&escape_xml(&rest[..start]));
+        html.push_str(&reference_html(command, &rest[label_start..label_start + len], equations));
+        rest = &rest[label_start + len + 1..];
+    }
+    html.push_str(&escape_xml(rest));
+    html
+}
+
+/// 处理正文文本中的公式引用和文献引用，没有需要处理的内容时返回 `None`
+fn render_text(
+    text: &str,
+    equations: &mut Equations,
+    citations: Option<&mut Citations>,
+) -> Option<String> {
+    match citations {
+        Some(citations) if text.contains("[@") => {
+            Some(citations.render_text(text, |plain| link_references(plain, equations)))
+        }
+        _ if text.contains("\\eqref{") || text.contains("\\ref{") => {
+            Some(link_references(text, equations))
+        }
+        _ => None,
+    }
+}
+
+/// 渲染公式，无法转换时记录警告
+fn render_math_checked(latex: &str, style: MathStyle, equations: &mut Equations) -> String {
+    equations.check_references(latex);
+    let html = render_math_with(latex, style, Some(equations));
+    if html.starts_with("<span class=\"math-error") {
+        equations.warnings.push(format!("公式无法渲染: {}", latex.trim()));
+    }
+    html
+}
+
+/// Markdown 渲染结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderedMarkdown {
+    /// 正文 HTML
+    pub html: String,
+    /// 目录
+    pub toc: Vec<TocEntry>,
+    /// 渲染时发现的问题（如未定义的公式标签），构建时输出为警告
+    pub warnings: Vec<String>,
+    /// `<!-- more -->` 之前的内容，不带标题锚点；没有分隔标记时为 `None`
+    pub excerpt: Option<String>,
+}
+
+/// 渲染 Markdown 为 HTML
+pub fn render_markdown(markdown: &str) -> String {
+    render_markdown_document(markdown).html
+}
+
+/// 由标题文本生成 `id`：保留中文等字母数字字符，空白替换为 `-`，
+/// 不会因为中文标题而得到空的 `id`
+pub fn heading_id(title: &str) -> String {
+    let id = slugify(title)
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if id.is_empty() {
+        "section".to_string()
+    } else {
+        id
+    }
+}
+
+/// 渲染 Markdown 为 HTML，同时为每个标题生成唯一的 `id` 和锚点链接，并返回目录
+///
+/// 代码块在构建时用 syntect 高亮，输出 `hl-` 前缀的类名，配色由 [`crate::highlight_css`] 提供；
+/// 公式在构建时转换为 MathML，见 [`crate::render_math`]
+///
+/// 标题可以用 `{#custom-id}` 显式指定 `id`；重复的 `id` 依次追加 `-1`、`-2`
+pub fn render_markdown_with_toc(markdown: &str) -> (String, Vec<TocEntry>) {
+    let rendered = render_markdown_document(markdown);
+    (rendered.html, rendered.toc)
+}
+
+/// 同 [`render_markdown_with_toc`]，并给带 `\label` 的块级公式编号、解析 `\eqref` 引用，
+/// 同时返回渲染中发现的问题
+pub fn render_markdown_document(markdown: &str) -> RenderedMarkdown {
+    render_markdown_with_citations(markdown, None)
+}
+
+/// 同 [`render_markdown_document`]，并把 `[@key]` 渲染为文献引用、在文末生成参考文献列表
+pub fn render_markdown_with_citations(markdown: &str, citations: Option<Citations>) -> RenderedMarkdown {
+    render_post_markdown(markdown, citations, None)
+}
+
+/// 页面包：文章的 slug 和资源文件列表
+type Bundle<'a> = (&'a str, &'a [String]);
+
+/// 把页面包中指向资源文件的相对图片地址改写为发布后的路径
+fn rewrite_bundle_image<'a>(event: Event<'a>, bundle: Option<Bundle>) -> Event<'a> {
+    match (event, bundle) {
+        (Event::Start(Tag::Image { link_type, dest_url, title, id }), Some((slug, assets))) => {
+            let dest_url = match bundle_resource(&dest_url).filter(|file| assets.contains(file)) {
+                Some(file) => CowStr::from(post_asset_path(slug, &file)),
+                None => dest_url,
+            };
+            Event::Start(Tag::Image { link_type, dest_url, title, id })
+        }
+        (event, _) => event,
+    }
+}
+
+fn render_post_markdown(
+    markdown: &str,
+    mut citations: Option<Citations>,
+    bundle: Option<Bundle>,
+) -> RenderedMarkdown {
+    // 合并相邻的文本事件，`[@key]` 这类语法会被解析器拆成多段
+    let mut events: Vec<Event> = Vec::new();
+    for event in Parser::new_ext(markdown, markdown_options()) {
+        let event = rewrite_bundle_image(event, bundle);
+        if let (Event::Text(text), Some(Event::Text(previous))) = (&event, events.last_mut()) {
+            *previous = CowStr::from(format!("{}{}", previous, text));
+            continue;
+        }
+        events.push(event);
+    }
+
+    // 第一遍：给块级公式编号，公式锚点先占用 id，标题不能再使用（如标题“Eq 1”）
+    let mut equations = Equations::default();
+    let mut numbers = Vec::new();
+    for event in &events {
+        if let Event::DisplayMath(latex) = event {
+            numbers.push(equations.register(latex));
+        }
+    }
+    let mut used: HashSet<String> = numbers.iter().flatten().map(|equation| equation.id.clone()).collect();
+
+    // 第二遍：收集标题文本并分配唯一 id
+    let mut toc = Vec::new();
+    let mut current: Option<(u8, Option<String>, String)> = None;
+    for event in &events {
+        match event {
+            Event::Start(Tag::Heading { level, id, .. }) => {
+                current = Some((*level as u8, id.as_deref().map(str::to_string), String::new()));
+            }
+            Event::Text(text) | Event::Code(text) | Event::InlineMath(text) => {
+                if let Some((_, _, title)) = current.as_mut() {
+                    title.push_str(text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((level, explicit, title)) = current.take() {
+                    let base = explicit.unwrap_or_else(|| heading_id(&title));
+                    let mut id = base.clone();
+                    let mut n = 0;
+                    while is_layout_id(&id) || !used.insert(id.clone()) {
+                        n += 1;
+                        id = format!("{}-{}", base, n);
+                    }
+                    toc.push(TocEntry {
+                        level,
+                        id,
+                        title: title.trim().to_string(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // 第三遍：写入 id，在标题开头插入锚点链接，在构建时高亮代码块、渲染公式
+    let mut headings = toc.iter();
+    let mut numbers = numbers.into_iter();
+    let mut output = Vec::with_capacity(events.len() + toc.len());
+    let mut code_block: Option<(String, String)> = None;
+    let mut more_at = None;
+    for event in events {
+        match event {
+            Event::Html(ref html) | Event::InlineHtml(ref html) if more_at.is_none() && html.contains(MORE_MARKER) => {
+                more_at = Some(output.len());
+                output.push(event);
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let lang = match kind {
+                    CodeBlockKind::Fenced(lang) => lang.to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                code_block = Some((lang, String::new()));
+            }
+            Event::Text(text) if code_block.is_some() => {
+                if let Some((_, code)) = code_block.as_mut() {
+                    code.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some((lang, code)) = code_block.take() {
+                    output.push(Event::Html(CowStr::from(highlight_code(&code, &lang))));
+                }
+            }
+            Event::Start(Tag::Heading {
+                level,
+                classes,
+                attrs,
+                ..
+            }) => {
+                let entry = headings.next().expect("标题数量与目录一致");
+                output.push(Event::Start(Tag::Heading {
+                    level,
+                    id: Some(CowStr::from(entry.id.clone())),
+                    classes,
+                    attrs,
+                }));
+                output.push(Event::Html(CowStr::from(format!(
+                    "<a class=\"heading-anchor\" href=\"#{}\" aria-hidden=\"true\">#</a>",
+                    entry.id
+                ))));
+            }
+            Event::InlineMath(latex) => {
+                let html = match parse_reference(&latex) {
+                    Some((command, label)) => reference_html(&command, &label, &mut equations),
+                    None => render_math_checked(&latex, MathStyle::Inline, &mut equations),
+                };
+                output.push(Event::InlineHtml(html.into()));
+            }
+            Event::DisplayMath(latex) => {
+                let mut html = render_math_checked(&latex, MathStyle::Display, &mut equations);
+                if let Some(equation) = numbers.next().flatten() {
+                    html = format!(
+                        "<span class=\"equation\" id=\"{}\">{}<span class=\"equation-number\">({})</span></span>",
+                        equation.id,
+                        html,
+                        escape_xml(&equation.number)
+                    );
+                }
+                output.push(Event::InlineHtml(html.into()));
+            }
+            Event::Text(text) => match render_text(&text, &mut equations, citations.as_mut()) {
+                Some(html) => output.push(Event::InlineHtml(html.into())),
+                None => output.push(Event::Text(text)),
+            },
+            event => output.push(event),
+        }
+    }
+
+    // 文末的参考文献列表，同时加入目录
+    let mut warnings = equations.warnings;
+    if let Some(citations) = citations {
+        if let Some(list) = citations.references_html() {
+            let title = "参考文献";
+            let mut id = heading_id(title);
+            let mut n = 0;
+            while !used.insert(id.clone()) {
+                n += 1;
+                id = format!("{}-{}", heading_id(title), n);
+            }
+            output.push(Event::Html(CowStr::from(format!(
+                "<section class=\"references\">\n<h2 id=\"{id}\"><a class=\"heading-anchor\" href=\"#{id}\" aria-hidden=\"true\">#</a>{title}</h2>\n{list}</section>\n",
+            ))));
+            toc.push(TocEntry {
+                level: 2,
+                id,
+                title: title.to_string(),
+            });
+        }
+        warnings.extend(citations.warnings);
+    }
+
+    let excerpt = more_at.map(|end| excerpt_html(&output[..end]));
+    let mut html_output = String::new();
+    html::push_html(&mut html_output, output.into_iter());
+
+    RenderedMarkdown {
+        html: html_output,
+        toc,
+        warnings,
+        excerpt,
+    }
+}
+
+/// 把分隔标记之前已渲染的事件输出为摘录：去掉标题的 `id` 和锚点链接，补全标记处未闭合的标签
+fn excerpt_html(events: &[Event]) -> String {
+    let mut open = Vec::new();
+    let mut excerpt = Vec::with_capacity(events.len());
+    let mut in_heading = false;
+    for event in events {
+        match event {
+            Event::Start(Tag::Heading { level, classes, attrs, .. }) => {
+                open.push(TagEnd::Heading(*level));
+                excerpt.push(Event::Start(Tag::Heading {
+                    level: *level,
+                    id: None,
+                    classes: classes.clone(),
+                    attrs: attrs.clone(),
+                }));
+                in_heading = true;
+                continue;
+            }
+            // 紧跟在标题开始之后的是锚点链接
+            Event::Html(_) if in_heading => {}
+            Event::Start(tag) => {
+                open.push(tag.to_end());
+                excerpt.push(event.clone());
+            }
+            Event::End(_) => {
+                open.pop();
+                excerpt.push(event.clone());
+            }
+            event => excerpt.push(event.clone()),
+        }
+        in_heading = false;
+    }
+    excerpt.extend(open.into_iter().rev().map(Event::End));
+
+    let mut html_output = String::new();
+    html::push_html(&mut html_output, excerpt.into_iter());
+    html_output.trim_end().to_string()
+}
+
+/// 统计 Markdown 正文的字数，不计代码块、公式和内嵌 HTML
+pub fn markdown_word_count(markdown: &str) -> WordCount {
+    let mut count = WordCount::default();
+    let mut in_code_block = false;
+    for event in Parser::new_ext(markdown, markdown_options()) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Text(text) | Event::Code(text) if !in_code_block => {
+                count += count_words(&text);
+            }
+            _ => {}
+        }
+    }
+    count
+}
+
+/// 摘录分隔标记：正文中该标记之前的内容作为摘录
+pub const MORE_MARKER: &str = "<!-- more -->";
+
+/// 没有摘要和分隔标记时，摘录截取的正文字数
+pub const EXCERPT_LENGTH: usize = 120;
+
+/// 生成文章摘录 HTML：frontmatter 的 `summary` 优先，其次是 `<!-- more -->` 之前的内容，
+/// 最后是正文纯文本的前 [`EXCERPT_LENGTH`] 个字
+///
+/// `more` 为渲染正文时得到的 [`RenderedMarkdown::excerpt`]，与正文共用公式和引用的编号；
+/// 摘录显示在列表页，不带标题锚点
+fn extract_excerpt(post: &Post, more: Option<String>) -> String {
+    if let Some(summary) = post.metadata.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        return escape_xml(summary.trim());
+    }
+    if let Some(more) = more {
+        return more;
+    }
+
+    // 纯文本摘录中的公式保留 LaTeX 源码
+    let mut html_output = String::new();
+    let events = Parser::new_ext(&post.content, markdown_options()).map(|event| match event {
+        Event::InlineMath(latex) | Event::DisplayMath(latex) => Event::Text(latex),
+        event => event,
+    });
+    html::push_html(&mut html_output, events);
+    let text = html_to_text(&html_output);
+    if text.is_empty() {
+        return String::new();
+    }
+    format!("<p>{}</p>", escape_xml(&truncate_chars(&text, EXCERPT_LENGTH)))
+}
+
+/// 处理 Markdown 文件：解析并渲染
+///
+/// 不识别页面包，扫描 blogs 目录时使用 [`process_post_file`]
+pub fn process_markdown_file(path: &Path) -> Result<Post> {
+    let file_name = path.file_name().map(|name| name.to_string_lossy().into_owned());
+    process_post_file(path, &file_name.unwrap_or_default())
+}
+
+/// 处理 blogs 目录中的文章：解析并渲染
+///
+/// `relative_path` 为相对于 blogs 目录的路径。子目录中的 `index.md` 是页面包，
+/// 同目录的其他文件成为文章的资源，正文和封面中指向它们的相对地址改写为发布后的路径
+pub fn process_post_file(path: &Path, relative_path: &str) -> Result<Post> {
+    // 解析文件
+    let mut post = parse_markdown_file(path)?;
+
+    let bundle_slug = is_bundle_index(relative_path).then(|| post.metadata.get_slug(relative_path));
+    if let Some(slug) = &bundle_slug {
+        post.assets = bundle_assets(path.parent().unwrap_or(Path::new("")));
+        let cover = post.metadata.cover_image.as_deref().and_then(bundle_resource);
+        if let Some(cover) = cover.filter(|cover| post.assets.contains(cover)) {
+            post.metadata.cover_image = Some(post_asset_path(slug, &cover));
+        }
+    }
+
+    // 参考文献文件相对于文章所在目录
+    let mut warnings = Vec::new();
+    let bibliography = post.metadata.bibliography.as_deref().and_then(|bib| {
+        let bib_path = path.parent().unwrap_or(Path::new("")).join(bib);
+        Bibliography::load(&bib_path)
+            .map_err(|e| warnings.push(format!("{:#}", e)))
+            .ok()
+    });
+    let citations = bibliography
+        .as_ref()
+        .map(|bibliography| Citations::new(bibliography, post.metadata.citation_style));
+
+    // 渲染 Markdown 为 HTML，同时生成目录
+    let bundle = bundle_slug.as_deref().map(|slug| (slug, post.assets.as_slice()));
+    let rendered = render_post_markdown(&post.content, citations, bundle);
+    let more = rendered.excerpt;
+    post.html_content = rendered.html;
+    post.toc = rendered.toc;
+    warnings.extend(rendered.warnings);
+    post.warnings = warnings;
+
+    // 字数与阅读时间
+    let count = markdown_word_count(&post.content);
+    post.word_count = count.total();
+    post.reading_time = count.reading_minutes();
+
+    // 摘录：与正文共用公式编号和引用编号
+    post.excerpt_html = extract_excerpt(&post, more);
+
+    // 清理文章中的原始 HTML，除非 Frontmatter 声明了 trusted: true
+    if !post.metadata.trusted {
+        post.html_content = sanitize_html(&post.html_content);
+        post.excerpt_html = sanitize_html(&post.excerpt_html);
+    }
+
+    Ok(post)
+}
+
+/// 页面包目录中除 Markdown 以外的所有文件（相对路径，按名称排序）
+///
+/// 含有 `index.md` 的子目录是另一个页面包，其中的文件不属于本文
+fn bundle_assets(dir: &Path) -> Vec<String> {
+    WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !(e.depth() > 0 && e.file_type().is_dir() && e.path().join("index.md").is_file()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.path().extension().and_then(|s| s.to_str()) != Some("md"))
+        .filter_map(|e| Some(e.path().strip_prefix(dir).ok()?.to_string_lossy().replace('\\', "/")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown() {
+        let markdown = "# 标题\n\n这是**粗体**文本。";
+        let html = render_markdown(markdown);
+        assert!(html.contains(r##"<h1 id="标题"><a class="heading-anchor" href="#标题" aria-hidden="true">#</a>标题</h1>"##));
+        assert!(html.contains("<strong>粗体</strong>"));
+    }
+
+    #[test]
+    fn test_markdown_word_count() {
+        let markdown = r#"# 标题
+
+正文 with some words，公式 $E = mc^2$ 不计入。
+
+```rust
+fn main() { println!("代码不计入"); }
+```
+
+$$
+\int_0^1 x \, dx
+$$
+
+价格是 \$5。
+"#;
+        let count = markdown_word_count(markdown);
+        // 标题 2 + 正文 2 + 公式 2 + 不计入 3 + 价格是 3
+        assert_eq!(count.cjk, 12);
+        // with some words + 5
+        assert_eq!(count.words, 4);
+    }
+
+    #[test]
+    fn test_extract_excerpt() {
+        let excerpt = |markdown: &str| {
+            let post = parse_markdown_content(markdown).unwrap();
+            extract_excerpt(&post, render_markdown_document(&post.content).excerpt)
+        };
+
+        // summary 优先
+        assert_eq!(
+            excerpt("---\ntitle: t\nsummary: \"<摘要>\"\n---\n正文\n<!-- more -->\n后文"),
+            "&lt;摘要&gt;"
+        );
+        // 其次是 <!-- more --> 之前的内容，渲染为 HTML
+        assert_eq!(
+            excerpt("---\ntitle: t\n---\n前文 **加粗**\n\n<!-- more -->\n\n后文"),
+            "<p>前文 <strong>加粗</strong></p>"
+        );
+        // 最后截取正文开头
+        let long = format!("---\ntitle: t\n---\n# 标题\n\n{}", "字".repeat(200));
+        let html = excerpt(&long);
+        assert!(html.starts_with("<p>标题 字字"));
+        assert!(html.ends_with("…</p>"));
+        assert_eq!(html_to_text(&html).chars().count(), EXCERPT_LENGTH + 1);
+        assert_eq!(excerpt("---\ntitle: t\n---\n"), "");
+    }
+
+    #[test]
+    fn test_excerpt_shares_equation_and_citation_state() {
+        let bibliography = Bibliography::parse("@misc{a, title = {A}, year = 2020}").unwrap();
+        let citations = Citations::new(&bibliography, Default::default());
+        let markdown = "# 引言\n\n见公式 \\eqref{eq:x} 与 [@a]。\n\n<!-- more -->\n\n$$x \\label{eq:x}$$\n";
+        let rendered = render_markdown_with_citations(markdown, Some(citations));
+        assert!(rendered.warnings.is_empty(), "{:?}", rendered.warnings);
+        let excerpt = rendered.excerpt.unwrap();
+        assert!(excerpt.starts_with("<h1>引言</h1>"), "{}", excerpt);
+        assert!(excerpt.contains(r##"<a class="eqref" href="#eq-1">(1)</a>"##), "{}", excerpt);
+        assert!(excerpt.contains(r##"[<a href="#ref-a">1</a>]"##), "{}", excerpt);
+        assert!(!excerpt.contains("references"));
+
+        // 行内的分隔标记：补全未闭合的段落
+        let rendered = render_markdown_document("前文 **加粗 <!-- more --> 后文**\n");
+        assert_eq!(rendered.excerpt.as_deref(), Some("<p>前文 <strong>加粗 </strong></p>"));
+        assert_eq!(render_markdown_document("`<!-- more -->`\n").excerpt, None);
+    }
+
+    #[test]
+    fn test_markdown_extensions() {
+        let html = render_markdown("术语\n: 解释\n\n水 ~2~ 与 平方 ^2^\n\n> [!NOTE]\n> 提示\n");
+        assert!(html.contains("<dl>"), "{}", html);
+        assert!(html.contains("<sub>2</sub>"), "{}", html);
+        assert!(html.contains("<sup>2</sup>"), "{}", html);
+        assert!(html.contains("markdown-alert-note"), "{}", html);
+
+        // 正文开头的 --- 块不是元数据
+        let post = parse_markdown_content("---\ntitle: t\n---\n---\n小节\n---\n").unwrap();
+        assert!(render_markdown(&post.content).contains("小节"));
+    }
+
+    #[test]
+    fn test_render_markdown_highlights_code() {
+        let html = render_markdown("```rust\nlet x = 1;\n```\n\n    indented <code>\n");
+        assert!(html.contains(r#"<pre class="code-block hl-code"><code class="language-rust"><span class="hl-source hl-rust">"#));
+        assert!(html.contains("<pre class=\"code-block hl-code\"><code>indented &lt;code&gt;\n</code></pre>"));
+        assert!(!html.contains("<pre><code"));
+    }
+
+    #[test]
+    fn test_render_markdown_math() {
+        let html = render_markdown("$a_i$ 和 $b_i$，$$x*y*z$$\n\n价格 \\$5");
+        assert!(!html.contains("<em>"));
+        assert_eq!(html.matches("<math ").count(), 3);
+        assert!(html.contains(r#"<math xmlns="http://www.w3.org/1998/Math/MathML" display="block">"#));
+        assert!(html.contains("价格 $5"));
+
+        let html = render_markdown("$\\unknown{x}$");
+        assert!(html.contains("<span class=\"math-error\" role=\"alert\">"));
+    }
+
+    #[test]
+    fn test_heading_ids_do_not_collide_with_equations() {
+        let rendered = render_markdown_document("## Eq 1 {#eq-2}\n\n## Eq 1\n\n$$x \\label{a}$$\n\n$$y \\label{b}$$\n\n\\eqref{a}\n");
+        let ids: Vec<&str> = rendered.toc.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["eq-2-1", "eq-1-1"]);
+        assert_eq!(rendered.html.matches(r#"id="eq-1""#).count(), 1);
+        assert!(rendered.html.contains(r#"<span class="equation" id="eq-1">"#));
+        assert!(rendered.html.contains(r##"<a class="eqref" href="#eq-1">(1)</a>"##));
+    }
+
+    #[test]
+    fn test_heading_ids_avoid_layout_ids() {
+        let rendered = render_markdown_document("# Main\n\n## 正文 {#article-content}\n");
+        let ids: Vec<&str> = rendered.toc.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["main-1", "article-content-1"]);
+        assert_eq!(sanitize_html(&rendered.html).matches(" id=\"").count(), 2);
+    }
+
+    #[test]
+    fn test_equation_numbering_and_references() {
+        let markdown = r"如 \eqref{eq:euler} 所示，公式 $\ref{eq:sum}$ 与 $x = \eqref{eq:euler}$：
+
+$$e^{i\pi} + 1 = 0 \label{eq:euler}$$
+
+$$a^2 + b^2 = c^2$$
+
+$$\begin{equation}\sum_{i=1}^n i \label{eq:sum}\end{equation}$$
+
+$$E = mc^2 \tag{*}\label{eq:sum}$$
+
+见 \eqref{eq:missing}。
+";
+        let rendered = render_markdown_document(markdown);
+        let html = &rendered.html;
+        assert!(html.contains(r##"如 <a class="eqref" href="#eq-1">(1)</a> 所示"##));
+        assert!(html.contains(r##"公式 <a class="eqref" href="#eq-2">2</a>"##));
+        assert!(html.contains(r##"<mrow class="eqref" href="#eq-1"><mtext>(1)</mtext></mrow>"##));
+        assert!(html.contains(r#"<span class="equation" id="eq-1"><math"#));
+        assert!(html.contains(r#"<span class="equation-number">(1)</span>"#));
+        assert!(html.contains(r#"<span class="equation-number">(2)</span>"#));
+        assert!(html.contains(r#"<span class="equation-number">(*)</span>"#));
+        assert_eq!(html.matches("equation-number").count(), 3);
+        assert!(html.contains(r#"<span class="eqref eqref-missing">(??)</span>"#));
+        assert_eq!(
+            rendered.warnings,
+            vec!["公式标签重复: eq:sum", "引用了未定义的公式标签: eq:missing"]
+        );
+    }
+
+    #[test]
+    fn test_citation_test_post() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../blogs/citation-test.md");
+        let post = process_markdown_file(&path).unwrap();
+        assert!(post.warnings.is_empty(), "{:?}", post.warnings);
+        let html = &post.html_content;
+        assert!(html.contains(r##"Knuth <span class="citation">[<a href="#ref-knuth1984">1</a>]</span>"##));
+        assert!(html.contains(r##"[<a href="#ref-lamport1994">2</a>, 第 2 章]"##));
+        assert!(html.contains(r##"[<a href="#ref-matsakis2014">3</a>, <a href="#ref-knuth1984">1</a>]"##));
+        assert!(html.contains(r#"<section class="references">"#));
+        assert_eq!(html.matches("<li id=\"ref-").count(), 3);
+        assert_eq!(post.toc.last().unwrap().id, "参考文献");
+    }
+
+    #[test]
+    fn test_citation_warnings() {
+        let bibliography = Bibliography::parse("@misc{a, title = {A}, year = 2020}").unwrap();
+        let citations = Citations::new(&bibliography, Default::default());
+        let rendered = render_markdown_with_citations("[@a] [@b]\n\n`[@a]`\n", Some(citations));
+        assert_eq!(rendered.warnings, vec!["未知的文献条目: b"]);
+        assert!(rendered.html.contains("<code>[@a]</code>"));
+
+        // 没有指定参考文献文件时保持原样
+        assert!(render_markdown("[@a]").contains("<p>[@a]</p>"));
+    }
+
+    #[test]
+    fn test_process_markdown_file_sanitizes_untrusted_html() {
+        let dir = std::env::temp_dir().join(format!("blog-content-sanitize-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let body = "# 标题\n\n<img src=x onerror=alert(1)>\n\n<script>alert(1)</script>\n\n$x^2$\n";
+
+        let path = dir.join("untrusted.md");
+        fs::write(&path, format!("---\ntitle: 测试\n---\n{}", body)).unwrap();
+        let post = process_markdown_file(&path).unwrap();
+        assert!(!post.html_content.contains("onerror"));
+        assert!(!post.html_content.contains("<script"));
+        assert!(post.html_content.contains(r##"<a class="heading-anchor" href="#标题" aria-hidden="true">"##));
+        assert!(post.html_content.contains("<math xmlns="));
+        assert!(!post.excerpt_html.contains("onerror"));
+
+        // <!-- more --> 之前的摘录同样经过清理
+        let path = dir.join("excerpt.md");
+        fs::write(&path, format!("---\ntitle: 测试\n---\n{}\n<!-- more -->\n\n后文\n", body)).unwrap();
+        let post = process_markdown_file(&path).unwrap();
+        assert!(post.excerpt_html.starts_with("<h1>标题</h1>"), "{}", post.excerpt_html);
+        assert!(!post.excerpt_html.contains("onerror"));
+        assert!(!post.excerpt_html.contains("<script"));
+        assert!(post.excerpt_html.contains("<math xmlns="));
+
+        let path = dir.join("trusted.md");
+        fs::write(&path, format!("---\ntitle: 测试\ntrusted: true\n---\n{}", body)).unwrap();
+        let post = process_markdown_file(&path).unwrap();
+        assert!(post.html_content.contains("<script>alert(1)</script>"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_math_test_post_renders_without_errors() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../blogs/math-test.md");
+        let post = process_markdown_file(&path).unwrap();
+        assert!(!post.html_content.contains("math-error"), "{}", post.html_content);
+        assert!(post.html_content.matches("<math ").count() > 20);
+        assert!(post.html_content.contains("<mi>ℝ</mi>"));
+        assert!(post.html_content.contains("<mtable columnalign=\"right left"));
+        assert!(post.html_content.contains(r##"Euler’s identity <a class="eqref" href="#eq-1">(1)</a>"##));
+        assert!(post.warnings.is_empty(), "{:?}", post.warnings);
+    }
+
+    #[test]
+    fn test_heading_id() {
+        assert_eq!(heading_id("Hello World"), "hello-world");
+        assert_eq!(heading_id("1. 快速开始 (Quick Start)"), "1-快速开始-quick-start");
+        assert_eq!(heading_id("!!!"), "section");
+    }
+
+    #[test]
+    fn test_render_markdown_with_toc() {
+        let markdown = "# 标题\n\n## 特性\n\n### `code` 用法\n\n## 特性\n\n## 自定义 {#custom}\n";
+        let (html, toc) = render_markdown_with_toc(markdown);
+        let ids: Vec<_> = toc.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["标题", "特性", "code-用法", "特性-1", "custom"]);
+        assert_eq!(toc[2].level, 3);
+        assert_eq!(toc[2].title, "code 用法");
+        assert_eq!(toc[4].title, "自定义");
+        assert!(html.contains(r#"<h2 id="特性-1">"#));
+        assert!(html.contains(r#"<h2 id="custom">"#));
+    }
+}
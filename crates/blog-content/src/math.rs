@@ -0,0 +1,1210 @@
+use crate::feed::escape_xml;
+use anyhow::{bail, Result};
+use std::collections::hash_map::{Entry, HashMap};
+
+/// 公式的排版方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathStyle {
+    /// 行内公式 `$…$`
+    Inline,
+    /// 块级公式 `$$…$$`
+    Display,
+}
+
+/// 带编号的块级公式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    /// 显示的编号：自动编号或 `\tag{…}` 指定的内容
+    pub number: String,
+    /// 公式的锚点 `id`
+    pub id: String,
+}
+
+/// 一篇文章中的公式编号与标签
+///
+/// 带 `\label{…}` 的块级公式和 `equation` 环境按出现顺序编号，`\tag{…}` 指定编号，
+/// `\notag` / `\nonumber` 不编号；`\eqref{…}` / `\ref{…}` 引用标签对应的编号
+#[derive(Debug, Clone, Default)]
+pub struct Equations {
+    labels: HashMap<String, Equation>,
+    count: usize,
+    anchors: usize,
+    /// 重复或未定义的标签等问题
+    pub warnings: Vec<String>,
+}
+
+impl Equations {
+    /// 登记一个块级公式，返回它的编号；不编号的公式返回 `None`
+    pub fn register(&mut self, latex: &str) -> Option<Equation> {
+        let mut labels = Vec::new();
+        let mut tag = None;
+        let mut numbered = false;
+        let mut suppressed = false;
+        for (name, arg) in scan_commands(latex) {
+            match (name.as_str(), arg) {
+                ("label", Some(label)) => labels.push(label),
+                ("tag", Some(custom)) => tag = Some(custom),
+                ("notag" | "nonumber", _) => suppressed = true,
+                ("begin", Some(env)) if env == "equation" => numbered = true,
+                _ => {}
+            }
+        }
+
+        let number = match tag {
+            Some(tag) => tag,
+            None if (numbered || !labels.is_empty()) && !suppressed => {
+                self.count += 1;
+                self.count.to_string()
+            }
+            None => {
+                for label in labels {
+                    self.warnings.push(format!("公式标签 {} 所在的公式没有编号", label));
+                }
+                return None;
+            }
+        };
+        self.anchors += 1;
+        let equation = Equation {
+            number,
+            id: format!("eq-{}", self.anchors),
+        };
+        for label in labels {
+            match self.labels.entry(label) {
+                Entry::Occupied(entry) => {
+                    self.warnings.push(format!("公式标签重复: {}", entry.key()));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(equation.clone());
+                }
+            }
+        }
+        Some(equation)
+    }
+
+    /// 查找标签对应的公式
+    pub fn get(&self, label: &str) -> Option<&Equation> {
+        self.labels.get(label)
+    }
+
+    /// 检查公式中引用的标签都已定义，未定义的记录为警告
+    pub fn check_references(&mut self, latex: &str) {
+        for (name, arg) in scan_commands(latex) {
+            if let ("eqref" | "ref", Some(label)) = (name.as_str(), arg) {
+                self.check_reference(&label);
+            }
+        }
+    }
+
+    /// 检查单个引用，未定义时记录警告
+    pub fn check_reference(&mut self, label: &str) -> Option<&Equation> {
+        if !self.labels.contains_key(label) {
+            self.warnings.push(format!("引用了未定义的公式标签: {}", label));
+        }
+        self.labels.get(label)
+    }
+}
+
+/// 引用显示的文本：`\eqref` 带括号，`\ref` 不带；未定义的标签显示为 `??`
+pub fn reference_text(command: &str, equation: Option<&Equation>) -> String {
+    let number = equation.map_or("??", |equation| equation.number.as_str());
+    if command == "eqref" {
+        format!("({})", number)
+    } else {
+        number.to_string()
+    }
+}
+
+/// 如果整个公式只是一个 `\eqref{…}` / `\ref{…}`，返回命令名和标签
+pub fn parse_reference(latex: &str) -> Option<(String, String)> {
+    let mut parser = MathParser::new(latex, None);
+    let Ok(Some(Token::Command(name))) = parser.next_token() else {
+        return None;
+    };
+    if name != "eqref" && name != "ref" {
+        return None;
+    }
+    let label = parser.parse_raw_group().ok()?;
+    matches!(parser.next_token(), Ok(None)).then_some((name, label))
+}
+
+/// 列出公式中的编号相关命令及其参数
+fn scan_commands(latex: &str) -> Vec<(String, Option<String>)> {
+    let mut parser = MathParser::new(latex, None);
+    let mut commands = Vec::new();
+    while let Ok(Some(token)) = parser.next_token() {
+        let Token::Command(name) = token else {
+            continue;
+        };
+        match name.as_str() {
+            "label" | "tag" | "eqref" | "ref" | "begin" => {
+                parser.skip_star();
+                commands.push((name, parser.parse_raw_group().ok()));
+            }
+            "notag" | "nonumber" => commands.push((name, None)),
+            _ => {}
+        }
+    }
+    commands
+}
+
+/// 把 LaTeX 公式转换为 MathML，不支持的命令或语法错误时返回错误
+///
+/// 输出中附带 `application/x-tex` 注解，复制公式时可以拿到原始 LaTeX
+pub fn latex_to_mathml(latex: &str, style: MathStyle) -> Result<String> {
+    latex_to_mathml_with(latex, style, None)
+}
+
+/// 同 [`latex_to_mathml`]，`\eqref{…}` / `\ref{…}` 使用文章中的公式编号
+pub fn latex_to_mathml_with(
+    latex: &str,
+    style: MathStyle,
+    equations: Option<&Equations>,
+) -> Result<String> {
+    let mut parser = MathParser::new(latex, equations);
+    let nodes = parser.parse_row(None)?;
+    parser.expect_end()?;
+
+    let mut body = String::new();
+    write_row(&nodes, &mut body);
+    let display = match style {
+        MathStyle::Inline => "",
+        MathStyle::Display => " display=\"block\"",
+    };
+    Ok(format!(
+        "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"{}><semantics><mrow>{}</mrow><annotation encoding=\"application/x-tex\">{}</annotation></semantics></math>",
+        display,
+        body,
+        escape_xml(latex.trim())
+    ))
+}
+
+/// 渲染公式；转换失败时输出醒目的错误框，显示原始 LaTeX 和错误原因
+pub fn render_math(latex: &str, style: MathStyle) -> String {
+    render_math_with(latex, style, None)
+}
+
+/// 同 [`render_math`]，`\eqref{…}` / `\ref{…}` 使用文章中的公式编号
+pub fn render_math_with(latex: &str, style: MathStyle, equations: Option<&Equations>) -> String {
+    match latex_to_mathml_with(latex, style, equations) {
+        Ok(mathml) => mathml,
+        Err(e) => {
+            let class = match style {
+                MathStyle::Inline => "math-error",
+                MathStyle::Display => "math-error math-error-display",
+            };
+            format!(
+                "<span class=\"{}\" role=\"alert\"><span class=\"math-error-message\">公式错误：{}</span><code>{}</code></span>",
+                class,
+                escape_xml(&e.to_string()),
+                escape_xml(latex.trim())
+            )
+        }
+    }
+}
+
+/// 公式语法树
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    /// 标识符，单个字母显示为斜体
+    Ident(String),
+    /// 直立的标识符（大写希腊字母、`\mathrm`）
+    Upright(String),
+    Number(String),
+    Op(String),
+    /// 括号等定界符；`\left`/`\right` 之外不随内容伸缩
+    Delim(String, bool),
+    /// `\big(` 等指定大小的定界符
+    Sized(String, &'static str),
+    /// 大型运算符，`true` 表示上下限写在正上方和正下方（如求和）
+    LargeOp(String, bool),
+    /// 函数名，`true` 表示上下限写在正下方（如 `\lim`）
+    Function(String, bool),
+    Text(String),
+    Space(&'static str),
+    Row(Vec<Node>),
+    Frac(Box<Node>, Box<Node>, Option<&'static str>),
+    Sqrt(Box<Node>, Option<Box<Node>>),
+    Scripts {
+        base: Box<Node>,
+        sub: Option<Box<Node>>,
+        sup: Option<Box<Node>>,
+    },
+    /// 上方（`false`）或下方（`true`）的重音符号
+    Accent(Box<Node>, &'static str, bool),
+    Fenced(Option<String>, Vec<Node>, Option<String>),
+    Table {
+        rows: Vec<Vec<Vec<Node>>>,
+        align: String,
+        spacing: Option<&'static str>,
+    },
+    Style(bool, Box<Node>),
+    /// 公式引用：显示文本和链接目标
+    Ref(String, Option<String>),
+}
+
+impl Node {
+    /// 上下标是否写在正上方和正下方
+    fn has_limits(&self) -> bool {
+        matches!(self, Node::LargeOp(_, true) | Node::Function(_, true))
+    }
+}
+
+/// 词法单元：普通字符或 `\command`
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Char(char),
+    Command(String),
+}
+
+/// 小写希腊字母及其他斜体符号
+const IDENTIFIERS: &[(&str, &str)] = &[
+    ("alpha", "α"), ("beta", "β"), ("gamma", "γ"), ("delta", "δ"),
+    ("epsilon", "ϵ"), ("varepsilon", "ε"), ("zeta", "ζ"), ("eta", "η"),
+    ("theta", "θ"), ("vartheta", "ϑ"), ("iota", "ι"), ("kappa", "κ"),
+    ("lambda", "λ"), ("mu", "μ"), ("nu", "ν"), ("xi", "ξ"),
+    ("pi", "π"), ("varpi", "ϖ"), ("rho", "ρ"), ("varrho", "ϱ"),
+    ("sigma", "σ"), ("varsigma", "ς"), ("tau", "τ"), ("upsilon", "υ"),
+    ("phi", "ϕ"), ("varphi", "φ"), ("chi", "χ"), ("psi", "ψ"),
+    ("omega", "ω"), ("ell", "ℓ"), ("hbar", "ℏ"), ("imath", "ı"),
+    ("jmath", "ȷ"), ("wp", "℘"),
+];
+
+/// 大写希腊字母及其他直立符号
+const UPRIGHT: &[(&str, &str)] = &[
+    ("Gamma", "Γ"), ("Delta", "Δ"), ("Theta", "Θ"), ("Lambda", "Λ"),
+    ("Xi", "Ξ"), ("Pi", "Π"), ("Sigma", "Σ"), ("Upsilon", "Υ"),
+    ("Phi", "Φ"), ("Psi", "Ψ"), ("Omega", "Ω"), ("infty", "∞"),
+    ("partial", "∂"), ("nabla", "∇"), ("emptyset", "∅"), ("varnothing", "∅"),
+    ("aleph", "ℵ"), ("Re", "ℜ"), ("Im", "ℑ"), ("angle", "∠"),
+    ("triangle", "△"), ("top", "⊤"), ("bot", "⊥"),
+];
+
+/// 二元运算符、关系符、箭头、逻辑与集合符号
+const OPERATORS: &[(&str, &str)] = &[
+    ("times", "×"), ("cdot", "⋅"), ("pm", "±"), ("mp", "∓"),
+    ("div", "÷"), ("ast", "∗"), ("star", "⋆"), ("circ", "∘"),
+    ("bullet", "∙"), ("oplus", "⊕"), ("ominus", "⊖"), ("otimes", "⊗"),
+    ("odot", "⊙"), ("wedge", "∧"), ("vee", "∨"), ("land", "∧"),
+    ("lor", "∨"), ("neg", "¬"), ("lnot", "¬"), ("eq", "="),
+    ("neq", "≠"), ("ne", "≠"), ("leq", "≤"), ("le", "≤"),
+    ("geq", "≥"), ("ge", "≥"), ("ll", "≪"), ("gg", "≫"),
+    ("equiv", "≡"), ("sim", "∼"), ("simeq", "≃"), ("approx", "≈"),
+    ("cong", "≅"), ("propto", "∝"), ("prec", "≺"), ("succ", "≻"),
+    ("perp", "⊥"), ("parallel", "∥"), ("mid", "∣"), ("forall", "∀"),
+    ("exists", "∃"), ("nexists", "∄"), ("in", "∈"), ("notin", "∉"),
+    ("ni", "∋"), ("subset", "⊂"), ("supset", "⊃"), ("subseteq", "⊆"),
+    ("supseteq", "⊇"), ("cup", "∪"), ("cap", "∩"), ("setminus", "∖"),
+    ("to", "→"), ("rightarrow", "→"), ("leftarrow", "←"), ("gets", "←"),
+    ("leftrightarrow", "↔"), ("Rightarrow", "⇒"), ("Leftarrow", "⇐"),
+    ("Leftrightarrow", "⇔"), ("implies", "⟹"), ("impliedby", "⟸"),
+    ("iff", "⟺"), ("mapsto", "↦"), ("longrightarrow", "⟶"),
+    ("longleftarrow", "⟵"), ("uparrow", "↑"), ("downarrow", "↓"),
+    ("ldots", "…"), ("dots", "…"), ("cdots", "⋯"), ("vdots", "⋮"),
+    ("ddots", "⋱"), ("colon", ":"), ("therefore", "∴"), ("because", "∵"),
+    ("prime", "′"), ("%", "%"), ("$", "$"), ("&", "&"),
+    ("#", "#"), ("_", "_"),
+];
+
+/// 定界符命令
+const DELIMITERS: &[(&str, &str)] = &[
+    ("{", "{"), ("}", "}"), ("lbrace", "{"), ("rbrace", "}"),
+    ("langle", "⟨"), ("rangle", "⟩"), ("|", "‖"), ("Vert", "‖"),
+    ("lVert", "‖"), ("rVert", "‖"), ("vert", "|"), ("lvert", "|"),
+    ("rvert", "|"), ("lfloor", "⌊"), ("rfloor", "⌋"), ("lceil", "⌈"),
+    ("rceil", "⌉"), ("backslash", "∖"),
+];
+
+/// 大型运算符；`true` 表示上下限写在正上方和正下方
+const LARGE_OPERATORS: &[(&str, &str, bool)] = &[
+    ("sum", "∑", true), ("prod", "∏", true), ("coprod", "∐", true),
+    ("bigcup", "⋃", true), ("bigcap", "⋂", true), ("bigoplus", "⨁", true),
+    ("bigotimes", "⨂", true), ("bigvee", "⋁", true), ("bigwedge", "⋀", true),
+    ("int", "∫", false), ("iint", "∬", false), ("iiint", "∭", false),
+    ("oint", "∮", false),
+];
+
+/// 函数名；`true` 表示上下限写在正下方
+const FUNCTIONS: &[(&str, bool)] = &[
+    ("sin", false), ("cos", false), ("tan", false), ("cot", false),
+    ("sec", false), ("csc", false), ("arcsin", false), ("arccos", false),
+    ("arctan", false), ("sinh", false), ("cosh", false), ("tanh", false),
+    ("log", false), ("ln", false), ("lg", false), ("exp", false),
+    ("dim", false), ("ker", false), ("deg", false), ("arg", false),
+    ("hom", false), ("lim", true), ("limsup", true), ("liminf", true),
+    ("max", true), ("min", true), ("sup", true), ("inf", true),
+    ("det", true), ("gcd", true), ("Pr", true),
+];
+
+/// 间距命令及宽度
+const SPACES: &[(&str, &str)] = &[
+    (",", "0.1667em"), ("thinspace", "0.1667em"), (":", "0.2222em"),
+    (">", "0.2222em"), ("medspace", "0.2222em"), (";", "0.2778em"),
+    ("thickspace", "0.2778em"), ("!", "-0.1667em"), (" ", "0.25em"),
+    ("quad", "1em"), ("qquad", "2em"),
+];
+
+/// 重音命令：符号、是否伸缩、是否在下方
+const ACCENTS: &[(&str, &str, bool, bool)] = &[
+    ("hat", "^", false, false), ("widehat", "^", true, false),
+    ("check", "ˇ", false, false), ("breve", "˘", false, false),
+    ("bar", "¯", false, false), ("overline", "¯", true, false),
+    ("tilde", "~", false, false), ("widetilde", "~", true, false),
+    ("vec", "→", false, false), ("overrightarrow", "→", true, false),
+    ("overleftarrow", "←", true, false), ("dot", "˙", false, false),
+    ("ddot", "¨", false, false), ("acute", "´", false, false),
+    ("grave", "`", false, false), ("underline", "_", true, true),
+];
+
+/// `\big(` 等定界符的高度
+const SIZES: &[(&str, &str)] = &[
+    ("big", "1.2em"), ("bigl", "1.2em"), ("bigr", "1.2em"), ("bigm", "1.2em"),
+    ("Big", "1.8em"), ("Bigl", "1.8em"), ("Bigr", "1.8em"), ("Bigm", "1.8em"),
+    ("bigg", "2.4em"), ("biggl", "2.4em"), ("biggr", "2.4em"), ("biggm", "2.4em"),
+    ("Bigg", "3em"), ("Biggl", "3em"), ("Biggr", "3em"), ("Biggm", "3em"),
+];
+
+fn lookup<T: Copy>(table: &[(&str, T)], name: &str) -> Option<T> {
+    table.iter().find(|(key, _)| *key == name).map(|(_, value)| *value)
+}
+
+/// 字体命令对应的字母变体
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Variant {
+    Normal,
+    Italic,
+    Bold,
+    DoubleStruck,
+    Script,
+    Fraktur,
+    SansSerif,
+}
+
+/// 把字母和数字映射到 Unicode 数学字母数字符号（浏览器对 `mathvariant` 的支持有限）
+fn styled_char(c: char, variant: Variant) -> char {
+    // 数学字母数字区中缺失、需要使用字母类符号区的字符
+    let hole = match (variant, c) {
+        (Variant::Script, 'B') => Some('ℬ'),
+        (Variant::Script, 'E') => Some('ℰ'),
+        (Variant::Script, 'F') => Some('ℱ'),
+        (Variant::Script, 'H') => Some('ℋ'),
+        (Variant::Script, 'I') => Some('ℐ'),
+        (Variant::Script, 'L') => Some('ℒ'),
+        (Variant::Script, 'M') => Some('ℳ'),
+        (Variant::Script, 'R') => Some('ℛ'),
+        (Variant::Script, 'e') => Some('ℯ'),
+        (Variant::Script, 'g') => Some('ℊ'),
+        (Variant::Script, 'o') => Some('ℴ'),
+        (Variant::Fraktur, 'C') => Some('ℭ'),
+        (Variant::Fraktur, 'H') => Some('ℌ'),
+        (Variant::Fraktur, 'I') => Some('ℑ'),
+        (Variant::Fraktur, 'R') => Some('ℜ'),
+        (Variant::Fraktur, 'Z') => Some('ℨ'),
+        (Variant::DoubleStruck, 'C') => Some('ℂ'),
+        (Variant::DoubleStruck, 'H') => Some('ℍ'),
+        (Variant::DoubleStruck, 'N') => Some('ℕ'),
+        (Variant::DoubleStruck, 'P') => Some('ℙ'),
+        (Variant::DoubleStruck, 'Q') => Some('ℚ'),
+        (Variant::DoubleStruck, 'R') => Some('ℝ'),
+        (Variant::DoubleStruck, 'Z') => Some('ℤ'),
+        _ => None,
+    };
+    if let Some(hole) = hole {
+        return hole;
+    }
+
+    // 各变体大写 A、小写 a、数字 0 的起始码位
+    let (upper, lower, digit) = match variant {
+        Variant::Bold => (0x1D400, 0x1D41A, Some(0x1D7CE)),
+        Variant::Script => (0x1D49C, 0x1D4B6, None),
+        Variant::Fraktur => (0x1D504, 0x1D51E, None),
+        Variant::DoubleStruck => (0x1D538, 0x1D552, Some(0x1D7D8)),
+        Variant::SansSerif => (0x1D5A0, 0x1D5BA, Some(0x1D7E2)),
+        Variant::Normal | Variant::Italic => return c,
+    };
+    let code = match c {
+        'A'..='Z' => upper + (c as u32 - 'A' as u32),
+        'a'..='z' => lower + (c as u32 - 'a' as u32),
+        '0'..='9' => match digit {
+            Some(digit) => digit + (c as u32 - '0' as u32),
+            None => return c,
+        },
+        _ => return c,
+    };
+    char::from_u32(code).unwrap_or(c)
+}
+
+/// 对字体命令的参数应用字母变体
+fn apply_variant(node: Node, variant: Variant) -> Node {
+    let map = |text: &str| text.chars().map(|c| styled_char(c, variant)).collect::<String>();
+    match node {
+        Node::Ident(text) if variant == Variant::Normal => Node::Upright(text),
+        Node::Ident(text) => Node::Ident(map(&text)),
+        Node::Upright(text) if variant == Variant::Italic => Node::Ident(text),
+        Node::Upright(text) => Node::Upright(map(&text)),
+        Node::Number(text) => Node::Number(map(&text)),
+        Node::Row(nodes) => Node::Row(
+            nodes
+                .into_iter()
+                .map(|node| apply_variant(node, variant))
+                .collect(),
+        ),
+        Node::Scripts { base, sub, sup } => Node::Scripts {
+            base: Box::new(apply_variant(*base, variant)),
+            sub,
+            sup,
+        },
+        node => node,
+    }
+}
+
+/// 递归下降的 LaTeX 公式解析器
+struct MathParser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    equations: Option<&'a Equations>,
+}
+
+impl<'a> MathParser<'a> {
+    fn new(latex: &str, equations: Option<&'a Equations>) -> Self {
+        Self {
+            chars: latex.chars().collect(),
+            pos: 0,
+            equations,
+        }
+    }
+
+    /// 跳过 `\tag*` 等命令名后的星号
+    fn skip_star(&mut self) {
+        if self.chars.get(self.pos) == Some(&'*') {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// 读取下一个词法单元（跳过空白）
+    fn next_token(&mut self) -> Result<Option<Token>> {
+        self.skip_whitespace();
+        let Some(&c) = self.chars.get(self.pos) else {
+            return Ok(None);
+        };
+        self.pos += 1;
+        if c != '\\' {
+            return Ok(Some(Token::Char(c)));
+        }
+
+        let start = self.pos;
+        while self.chars.get(self.pos).is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            // 单个非字母字符组成的命令，如 `\,`、`\{`、`\\`
+            match self.chars.get(self.pos) {
+                Some(_) => self.pos += 1,
+                None => bail!("公式以单独的 \\ 结尾"),
+            }
+        }
+        Ok(Some(Token::Command(self.chars[start..self.pos].iter().collect())))
+    }
+
+    fn peek_token(&mut self) -> Result<Option<Token>> {
+        let pos = self.pos;
+        let token = self.next_token();
+        self.pos = pos;
+        token
+    }
+
+    /// 解析一行公式，遇到 `}`、`&`、`\\`、`\end`、`\right` 或 `stop` 时停止（不消耗）
+    fn parse_row(&mut self, stop: Option<char>) -> Result<Vec<Node>> {
+        let mut nodes = Vec::new();
+        loop {
+            match self.peek_token()? {
+                None => break,
+                Some(Token::Char('}' | '&')) => break,
+                Some(Token::Char(c)) if Some(c) == stop => break,
+                Some(Token::Command(name))
+                    if matches!(name.as_str(), "\\" | "end" | "right") =>
+                {
+                    break
+                }
+                Some(Token::Command(name))
+                    if matches!(name.as_str(), "displaystyle" | "textstyle") =>
+                {
+                    // 样式命令作用于本行剩余部分
+                    self.next_token()?;
+                    let rest = self.parse_row(stop)?;
+                    nodes.push(Node::Style(name == "displaystyle", Box::new(Node::Row(rest))));
+                    break;
+                }
+                Some(_) => nodes.push(self.parse_scripts()?),
+            }
+        }
+        Ok(nodes)
+    }
+
+    /// 解析一个元素及其上下标和撇号
+    fn parse_scripts(&mut self) -> Result<Node> {
+        let base = self.parse_atom()?;
+        let mut sub = None;
+        let mut sup = None;
+        let mut primes = String::new();
+        loop {
+            match self.peek_token()? {
+                Some(Token::Char('_')) => {
+                    self.next_token()?;
+                    if sub.is_some() {
+                        bail!("重复的下标");
+                    }
+                    sub = Some(Box::new(self.parse_arg()?));
+                }
+                Some(Token::Char('^')) => {
+                    self.next_token()?;
+                    if sup.is_some() {
+                        bail!("重复的上标");
+                    }
+                    sup = Some(Box::new(self.parse_arg()?));
+                }
+                Some(Token::Char('\'')) if sup.is_none() => {
+                    self.next_token()?;
+                    primes.push('′');
+                }
+                _ => break,
+            }
+        }
+
+        if !primes.is_empty() {
+            let prime = Node::Op(primes);
+            sup = Some(Box::new(match sup {
+                Some(sup) => Node::Row(vec![prime, *sup]),
+                None => prime,
+            }));
+        }
+        if sub.is_none() && sup.is_none() {
+            return Ok(base);
+        }
+        Ok(Node::Scripts {
+            base: Box::new(base),
+            sub,
+            sup,
+        })
+    }
+
+    /// 解析命令参数或上下标：花括号分组，或单个字符 / 命令
+    fn parse_arg(&mut self) -> Result<Node> {
+        match self.peek_token()? {
+            None | Some(Token::Char('}' | '&' | '^' | '_')) => bail!("命令或上下标缺少参数"),
+            Some(Token::Char(c)) if c.is_ascii_digit() => {
+                self.next_token()?;
+                Ok(Node::Number(c.to_string()))
+            }
+            Some(_) => self.parse_atom(),
+        }
+    }
+
+    fn expect_char(&mut self, expected: char) -> Result<()> {
+        match self.next_token()? {
+            Some(Token::Char(c)) if c == expected => Ok(()),
+            Some(Token::Char(c)) => bail!("需要 {}，实际是 {}", expected, c),
+            Some(Token::Command(name)) => bail!("需要 {}，实际是 \\{}", expected, name),
+            None => bail!("缺少 {}", expected),
+        }
+    }
+
+    /// 原样读取花括号中的文本（`\text`、环境名等）
+    fn parse_raw_group(&mut self) -> Result<String> {
+        self.skip_whitespace();
+        if self.chars.get(self.pos) != Some(&'{') {
+            bail!("缺少 {{");
+        }
+        self.pos += 1;
+        let mut depth = 0;
+        let mut text = String::new();
+        while let Some(&c) = self.chars.get(self.pos) {
+            self.pos += 1;
+            match c {
+                '\\' => {
+                    // 转义字符取字面值
+                    if let Some(&next) = self.chars.get(self.pos) {
+                        self.pos += 1;
+                        text.push(next);
+                    }
+                }
+                '{' => {
+                    depth += 1;
+                    text.push(c);
+                }
+                '}' if depth == 0 => return Ok(text),
+                '}' => {
+                    depth -= 1;
+                    text.push(c);
+                }
+                _ => text.push(c),
+            }
+        }
+        bail!("缺少 }}")
+    }
+
+    /// 整个公式解析完后不应有剩余内容
+    fn expect_end(&mut self) -> Result<()> {
+        match self.next_token()? {
+            None => Ok(()),
+            Some(Token::Char('}')) => bail!("多余的 }}"),
+            Some(Token::Char('&')) => bail!("& 只能用在矩阵或对齐环境中"),
+            Some(Token::Command(name)) if name == "\\" => bail!("\\\\ 只能用在矩阵或对齐环境中"),
+            Some(Token::Command(name)) if name == "right" => bail!("\\right 缺少对应的 \\left"),
+            Some(Token::Command(name)) if name == "end" => bail!("多余的 \\end"),
+            Some(Token::Char(c)) => bail!("无法解析的字符: {}", c),
+            Some(Token::Command(name)) => bail!("无法解析的命令: \\{}", name),
+        }
+    }
+
+    fn parse_atom(&mut self) -> Result<Node> {
+        let pos = self.pos;
+        let token = self.next_token()?.ok_or_else(|| anyhow::anyhow!("公式意外结束"))?;
+        match token {
+            Token::Char('{') => {
+                let nodes = self.parse_row(None)?;
+                self.expect_char('}')?;
+                Ok(Node::Row(nodes))
+            }
+            Token::Char('^' | '_') => {
+                // 没有底数的上下标，如 `{}^{14}C`
+                self.pos = pos;
+                Ok(Node::Row(Vec::new()))
+            }
+            Token::Char(c) if c.is_ascii_digit() || c == '.' => {
+                let mut number = c.to_string();
+                while let Some(&c) = self.chars.get(self.pos) {
+                    let decimal_point =
+                        c == '.' && self.chars.get(self.pos + 1).is_some_and(char::is_ascii_digit);
+                    if !c.is_ascii_digit() && !decimal_point {
+                        break;
+                    }
+                    number.push(c);
+                    self.pos += 1;
+                }
+                if number == "." {
+                    Ok(Node::Op(number))
+                } else {
+                    Ok(Node::Number(number))
+                }
+            }
+            Token::Char(c) if c.is_alphabetic() => Ok(Node::Ident(c.to_string())),
+            Token::Char('(' | ')' | '[' | ']' | '|') => Ok(Node::Delim(token_text(&token), false)),
+            Token::Char('-') => Ok(Node::Op("−".to_string())),
+            Token::Char('*') => Ok(Node::Op("∗".to_string())),
+            Token::Char('\'') => Ok(Node::Op("′".to_string())),
+            Token::Char('~') => Ok(Node::Space("0.25em")),
+            Token::Char(c @ ('}' | '&' | '#' | '%' | '$')) => bail!("此处不能使用 {}", c),
+            Token::Char(c) => Ok(Node::Op(c.to_string())),
+            Token::Command(name) => self.parse_command(&name),
+        }
+    }
+
+    fn parse_command(&mut self, name: &str) -> Result<Node> {
+        if let Some(text) = lookup(IDENTIFIERS, name) {
+            return Ok(Node::Ident(text.to_string()));
+        }
+        if let Some(text) = lookup(UPRIGHT, name) {
+            return Ok(Node::Upright(text.to_string()));
+        }
+        if let Some(text) = lookup(OPERATORS, name) {
+            return Ok(Node::Op(text.to_string()));
+        }
+        if let Some(text) = lookup(DELIMITERS, name) {
+            return Ok(Node::Delim(text.to_string(), false));
+        }
+        if let Some((_, text, limits)) = LARGE_OPERATORS.iter().find(|(key, ..)| *key == name) {
+            return Ok(Node::LargeOp(text.to_string(), *limits));
+        }
+        if let Some(limits) = lookup(FUNCTIONS, name) {
+            return Ok(Node::Function(name.to_string(), limits));
+        }
+        if let Some(width) = lookup(SPACES, name) {
+            return Ok(Node::Space(width));
+        }
+        if let Some((_, mark, stretchy, under)) = ACCENTS.iter().find(|(key, ..)| *key == name) {
+            let base = self.parse_arg()?;
+            let mark = if *stretchy { mark } else { non_stretchy(mark) };
+            return Ok(Node::Accent(Box::new(base), mark, *under));
+        }
+        if let Some(size) = lookup(SIZES, name) {
+            let delimiter = self.parse_delimiter()?.unwrap_or_default();
+            return Ok(Node::Sized(delimiter, size));
+        }
+
+        let variant = match name {
+            "mathrm" | "operatorname" => Some(Variant::Normal),
+            "mathit" => Some(Variant::Italic),
+            "mathbf" | "boldsymbol" | "bm" => Some(Variant::Bold),
+            "mathbb" => Some(Variant::DoubleStruck),
+            "mathcal" | "mathscr" => Some(Variant::Script),
+            "mathfrak" => Some(Variant::Fraktur),
+            "mathsf" => Some(Variant::SansSerif),
+            _ => None,
+        };
+        if let Some(variant) = variant {
+            if name == "operatorname" {
+                return Ok(Node::Function(self.parse_raw_group()?, false));
+            }
+            return Ok(apply_variant(self.parse_arg()?, variant));
+        }
+
+        match name {
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let numerator = self.parse_arg()?;
+                let denominator = self.parse_arg()?;
+                let frac = Node::Frac(Box::new(numerator), Box::new(denominator), None);
+                Ok(match name {
+                    "dfrac" | "cfrac" => Node::Style(true, Box::new(frac)),
+                    "tfrac" => Node::Style(false, Box::new(frac)),
+                    _ => frac,
+                })
+            }
+            "binom" => {
+                let top = self.parse_arg()?;
+                let bottom = self.parse_arg()?;
+                let frac = Node::Frac(Box::new(top), Box::new(bottom), Some("0"));
+                Ok(Node::Fenced(Some("(".to_string()), vec![frac], Some(")".to_string())))
+            }
+            "sqrt" => {
+                self.skip_whitespace();
+                let index = if self.chars.get(self.pos) == Some(&'[') {
+                    self.pos += 1;
+                    let index = self.parse_row(Some(']'))?;
+                    self.expect_char(']')?;
+                    Some(Box::new(Node::Row(index)))
+                } else {
+                    None
+                };
+                Ok(Node::Sqrt(Box::new(self.parse_arg()?), index))
+            }
+            "left" => {
+                let open = self.parse_delimiter()?;
+                let body = self.parse_row(None)?;
+                match self.next_token()? {
+                    Some(Token::Command(name)) if name == "right" => {}
+                    _ => bail!("\\left 缺少对应的 \\right"),
+                }
+                let close = self.parse_delimiter()?;
+                Ok(Node::Fenced(open, body, close))
+            }
+            "middle" => Ok(Node::Delim(self.parse_delimiter()?.unwrap_or_default(), true)),
+            "text" | "textrm" | "textnormal" | "mbox" | "textit" | "textbf" => {
+                Ok(Node::Text(self.parse_raw_group()?))
+            }
+            "not" => match self.parse_atom()? {
+                Node::Op(op) => Ok(Node::Op(format!("{}\u{338}", op))),
+                _ => bail!("\\not 只能用于关系符"),
+            },
+            "begin" => {
+                let env = self.parse_raw_group()?;
+                self.parse_environment(&env)
+            }
+            // 编号相关的命令不显示，编号由 Equations 统一处理
+            "label" | "tag" => {
+                self.skip_star();
+                self.parse_raw_group()?;
+                Ok(Node::Row(Vec::new()))
+            }
+            "notag" | "nonumber" => Ok(Node::Row(Vec::new())),
+            "eqref" | "ref" => {
+                let label = self.parse_raw_group()?;
+                let equation = self.equations.and_then(|equations| equations.get(&label));
+                Ok(Node::Ref(
+                    reference_text(name, equation),
+                    equation.map(|equation| equation.id.clone()),
+                ))
+            }
+            _ => bail!("不支持的命令: \\{}", name),
+        }
+    }
+
+    /// 读取 `\left`、`\right`、`\big` 等之后的定界符；`.` 表示不显示
+    fn parse_delimiter(&mut self) -> Result<Option<String>> {
+        let token = self.next_token()?;
+        match &token {
+            Some(Token::Char('.')) => Ok(None),
+            Some(Token::Char('(' | ')' | '[' | ']' | '|' | '/' | '<' | '>')) => {
+                let text = match token_text(token.as_ref().unwrap()).as_str() {
+                    "<" => "⟨".to_string(),
+                    ">" => "⟩".to_string(),
+                    text => text.to_string(),
+                };
+                Ok(Some(text))
+            }
+            Some(Token::Command(name)) => match lookup(DELIMITERS, name) {
+                Some(text) => Ok(Some(text.to_string())),
+                None => bail!("无效的定界符: \\{}", name),
+            },
+            Some(Token::Char(c)) => bail!("无效的定界符: {}", c),
+            None => bail!("缺少定界符"),
+        }
+    }
+
+    /// 解析 `\begin{env}` 之后的环境内容，直到 `\end{env}`
+    fn parse_environment(&mut self, env: &str) -> Result<Node> {
+        let (open, close, align, spacing) = match env {
+            "matrix" | "smallmatrix" => (None, None, String::new(), None),
+            "pmatrix" => (Some("("), Some(")"), String::new(), None),
+            "bmatrix" => (Some("["), Some("]"), String::new(), None),
+            "Bmatrix" => (Some("{"), Some("}"), String::new(), None),
+            "vmatrix" => (Some("|"), Some("|"), String::new(), None),
+            "Vmatrix" => (Some("‖"), Some("‖"), String::new(), None),
+            "cases" => (Some("{"), None, "left left".to_string(), None),
+            "aligned" | "align" | "align*" | "split" => (
+                None,
+                None,
+                "right left right left right left".to_string(),
+                Some("0em 2em 0em 2em 0em"),
+            ),
+            "gathered" | "gather" | "gather*" => (None, None, String::new(), None),
+            "array" => {
+                let spec = self.parse_raw_group()?;
+                let align = spec
+                    .chars()
+                    .filter_map(|c| match c {
+                        'l' => Some("left"),
+                        'c' => Some("center"),
+                        'r' => Some("right"),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                (None, None, align, None)
+            }
+            "equation" | "equation*" => {
+                let body = self.parse_row(None)?;
+                self.expect_environment_end(env)?;
+                return Ok(Node::Row(body));
+            }
+            _ => bail!("不支持的环境: {}", env),
+        };
+
+        let mut rows = Vec::new();
+        let mut row = Vec::new();
+        loop {
+            row.push(self.parse_row(None)?);
+            match self.next_token()? {
+                Some(Token::Char('&')) => {}
+                Some(Token::Command(name)) if name == "\\" => {
+                    self.skip_row_spacing();
+                    rows.push(std::mem::take(&mut row));
+                }
+                Some(Token::Command(name)) if name == "end" => {
+                    self.pos -= "\\end".len();
+                    self.expect_environment_end(env)?;
+                    // 末尾的 `\\` 不产生空行
+                    if !(row.len() == 1 && row[0].is_empty()) {
+                        rows.push(row);
+                    }
+                    break;
+                }
+                Some(Token::Char('}')) => bail!("环境 {} 中有多余的 }}", env),
+                Some(Token::Command(name)) if name == "right" => {
+                    bail!("环境 {} 中有多余的 \\right", env)
+                }
+                _ => bail!("环境 {} 缺少 \\end{{{}}}", env, env),
+            }
+        }
+
+        let table = Node::Table {
+            rows,
+            align,
+            spacing,
+        };
+        Ok(match (open, close) {
+            (None, None) => table,
+            (open, close) => Node::Fenced(
+                open.map(str::to_string),
+                vec![table],
+                close.map(str::to_string),
+            ),
+        })
+    }
+
+    /// 读取 `\end{env}` 并检查环境名一致
+    fn expect_environment_end(&mut self, env: &str) -> Result<()> {
+        match self.next_token()? {
+            Some(Token::Command(name)) if name == "end" => {}
+            _ => bail!("环境 {} 缺少 \\end{{{}}}", env, env),
+        }
+        let end = self.parse_raw_group()?;
+        if end != env {
+            bail!("环境不匹配：\\begin{{{}}} 与 \\end{{{}}}", env, end);
+        }
+        Ok(())
+    }
+
+    /// 跳过 `\\[2pt]` 这类行距参数
+    fn skip_row_spacing(&mut self) {
+        let pos = self.pos;
+        self.skip_whitespace();
+        if self.chars.get(self.pos) == Some(&'[') {
+            if let Some(end) = self.chars[self.pos..].iter().position(|&c| c == ']') {
+                self.pos += end + 1;
+                return;
+            }
+        }
+        self.pos = pos;
+    }
+}
+
+fn token_text(token: &Token) -> String {
+    match token {
+        Token::Char(c) => c.to_string(),
+        Token::Command(name) => format!("\\{}", name),
+    }
+}
+
+/// 普通重音符号使用的组合字符形式
+fn non_stretchy(mark: &'static str) -> &'static str {
+    match mark {
+        "^" => "ˆ",
+        "~" => "˜",
+        mark => mark,
+    }
+}
+
+fn write_row(nodes: &[Node], out: &mut String) {
+    for node in nodes {
+        write_node(node, out);
+    }
+}
+
+/// 输出为单个 MathML 元素，多个子元素时包在 `<mrow>` 中
+fn write_wrapped(node: &Node, out: &mut String) {
+    match node {
+        Node::Row(nodes) if nodes.len() == 1 => write_node(&nodes[0], out),
+        Node::Row(_) => {
+            out.push_str("<mrow>");
+            write_node(node, out);
+            out.push_str("</mrow>");
+        }
+        node => write_node(node, out),
+    }
+}
+
+fn write_node(node: &Node, out: &mut String) {
+    match node {
+        Node::Ident(text) => out.push_str(&format!("<mi>{}</mi>", escape_xml(text))),
+        Node::Upright(text) => {
+            out.push_str(&format!("<mi mathvariant=\"normal\">{}</mi>", escape_xml(text)))
+        }
+        Node::Number(text) => out.push_str(&format!("<mn>{}</mn>", escape_xml(text))),
+        Node::Op(text) => out.push_str(&format!("<mo>{}</mo>", escape_xml(text))),
+        Node::Delim(text, stretchy) => out.push_str(&format!(
+            "<mo stretchy=\"{}\">{}</mo>",
+            stretchy,
+            escape_xml(text)
+        )),
+        Node::Sized(text, size) => out.push_str(&format!(
+            "<mo stretchy=\"true\" symmetric=\"true\" minsize=\"{}\" maxsize=\"{}\">{}</mo>",
+            size,
+            size,
+            escape_xml(text)
+        )),
+        Node::LargeOp(text, limits) => out.push_str(&format!(
+            "<mo largeop=\"true\" movablelimits=\"{}\">{}</mo>",
+            limits,
+            escape_xml(text)
+        )),
+        Node::Function(name, true) => out.push_str(&format!(
+            "<mo form=\"prefix\" movablelimits=\"true\">{}</mo>",
+            escape_xml(name)
+        )),
+        Node::Function(name, false) => out.push_str(&format!("<mi>{}</mi>", escape_xml(name))),
+        Node::Text(text) => {
+            // mtext 首尾的空白会被忽略，改为不换行空格
+            out.push_str(&format!("<mtext>{}</mtext>", escape_xml(&text.replace(' ', "\u{a0}"))))
+        }
+        Node::Space(width) => out.push_str(&format!("<mspace width=\"{}\"></mspace>", width)),
+        Node::Row(nodes) => write_row(nodes, out),
+        Node::Frac(numerator, denominator, thickness) => {
+            match thickness {
+                Some(thickness) => {
+                    out.push_str(&format!("<mfrac linethickness=\"{}\">", thickness))
+                }
+                None => out.push_str("<mfrac>"),
+            }
+            write_wrapped(numerator, out);
+            write_wrapped(denominator, out);
+            out.push_str("</mfrac>");
+        }
+        Node::Sqrt(body, None) => {
+            out.push_str("<msqrt>");
+            write_wrapped(body, out);
+            out.push_str("</msqrt>");
+        }
+        Node::Sqrt(body, Some(index)) => {
+            out.push_str("<mroot>");
+            write_wrapped(body, out);
+            write_wrapped(index, out);
+            out.push_str("</mroot>");
+        }
+        Node::Scripts { base, sub, sup } => {
+            let (under, over) = if base.has_limits() {
+                ("munder", "mover")
+            } else {
+                ("msub", "msup")
+            };
+            let tag = match (sub, sup) {
+                (Some(_), Some(_)) if base.has_limits() => "munderover",
+                (Some(_), Some(_)) => "msubsup",
+                (Some(_), None) => under,
+                _ => over,
+            };
+            out.push_str(&format!("<{}>", tag));
+            write_wrapped(base, out);
+            for script in [sub, sup].into_iter().flatten() {
+                write_wrapped(script, out);
+            }
+            out.push_str(&format!("</{}>", tag));
+        }
+        Node::Accent(base, mark, under) => {
+            let (tag, attr) = if *under {
+                ("munder", "accentunder")
+            } else {
+                ("mover", "accent")
+            };
+            out.push_str(&format!("<{} {}=\"true\">", tag, attr));
+            write_wrapped(base, out);
+            out.push_str(&format!("<mo>{}</mo></{}>", escape_xml(mark), tag));
+        }
+        Node::Fenced(open, body, close) => {
+            out.push_str("<mrow>");
+            if let Some(open) = open {
+                out.push_str(&format!(
+                    "<mo fence=\"true\" form=\"prefix\" stretchy=\"true\">{}</mo>",
+                    escape_xml(open)
+                ));
+            }
+            write_row(body, out);
+            if let Some(close) = close {
+                out.push_str(&format!(
+                    "<mo fence=\"true\" form=\"postfix\" stretchy=\"true\">{}</mo>",
+                    escape_xml(close)
+                ));
+            }
+            out.push_str("</mrow>");
+        }
+        Node::Table {
+            rows,
+            align,
+            spacing,
+        } => {
+            out.push_str("<mtable");
+            if !align.is_empty() {
+                out.push_str(&format!(" columnalign=\"{}\"", align));
+            }
+            if let Some(spacing) = spacing {
+                out.push_str(&format!(" columnspacing=\"{}\"", spacing));
+            }
+            out.push('>');
+            for row in rows {
+                out.push_str("<mtr>");
+                for cell in row {
+                    out.push_str("<mtd>");
+                    write_row(cell, out);
+                    out.push_str("</mtd>");
+                }
+                out.push_str("</mtr>");
+            }
+            out.push_str("</mtable>");
+        }
+        Node::Style(display, body) => {
+            out.push_str(&format!("<mstyle displaystyle=\"{}\">", display));
+            write_wrapped(body, out);
+            out.push_str("</mstyle>");
+        }
+        Node::Ref(text, Some(id)) => out.push_str(&format!(
+            "<mrow class=\"eqref\" href=\"#{}\"><mtext>{}</mtext></mrow>",
+            id,
+            escape_xml(text)
+        )),
+        Node::Ref(text, None) => out.push_str(&format!("<mtext>{}</mtext>", escape_xml(text))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mathml(latex: &str) -> String {
+        let html = latex_to_mathml(latex, MathStyle::Inline).unwrap();
+        let start = html.find("<mrow>").unwrap() + "<mrow>".len();
+        let end = html.rfind("</mrow><annotation").unwrap();
+        html[start..end].to_string()
+    }
+
+    #[test]
+    fn test_basic_expressions() {
+        assert_eq!(
+            mathml("a^2 + b_i"),
+            "<msup><mi>a</mi><mn>2</mn></msup><mo>+</mo><msub><mi>b</mi><mi>i</mi></msub>"
+        );
+        assert_eq!(mathml("x^23"), "<msup><mi>x</mi><mn>2</mn></msup><mn>3</mn>");
+        assert_eq!(
+            mathml(r"\frac{1}{2}"),
+            "<mfrac><mn>1</mn><mn>2</mn></mfrac>"
+        );
+        assert_eq!(
+            mathml(r"\sqrt[3]{x}"),
+            "<mroot><mi>x</mi><mn>3</mn></mroot>"
+        );
+        assert_eq!(
+            mathml("f''(x)"),
+            "<msup><mi>f</mi><mo>′′</mo></msup><mo stretchy=\"false\">(</mo><mi>x</mi><mo stretchy=\"false\">)</mo>"
+        );
+    }
+
+    #[test]
+    fn test_limits_and_fonts() {
+        assert_eq!(
+            mathml(r"\sum_{i=1}^n"),
+            "<munderover><mo largeop=\"true\" movablelimits=\"true\">∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover>"
+        );
+        assert!(mathml(r"\int_0^1").starts_with("<msubsup>"));
+        assert_eq!(mathml(r"\mathbb{R}\mathcal{L}"), "<mi>ℝ</mi><mi>ℒ</mi>");
+        assert_eq!(mathml(r"\mathbf{E}"), "<mi>𝐄</mi>");
+        assert_eq!(mathml(r"\text{if } x"), "<mtext>if\u{a0}</mtext><mi>x</mi>");
+    }
+
+    #[test]
+    fn test_environments() {
+        let matrix = mathml(r"\begin{pmatrix} 1 & 2 \\ 3 & 4 \\ \end{pmatrix}");
+        assert!(matrix.starts_with("<mrow><mo fence=\"true\" form=\"prefix\" stretchy=\"true\">(</mo><mtable>"));
+        assert_eq!(matrix.matches("<mtr>").count(), 2);
+        assert_eq!(matrix.matches("<mtd>").count(), 4);
+
+        let aligned = mathml(r"\begin{aligned} a &= b \\ c &= d \end{aligned}");
+        assert!(aligned.contains("columnalign=\"right left"));
+        assert!(latex_to_mathml(r"\begin{cases} 1 \end{aligned}", MathStyle::Display).is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |latex: &str| latex_to_mathml(latex, MathStyle::Inline).unwrap_err().to_string();
+        assert_eq!(error(r"\foo x"), "不支持的命令: \\foo");
+        assert_eq!(error(r"\frac{1}"), "命令或上下标缺少参数");
+        assert_eq!(error(r"\left( x"), "\\left 缺少对应的 \\right");
+        assert_eq!(error("a}"), "多余的 }");
+        assert_eq!(error("x^1^2"), "重复的上标");
+
+        let html = render_math(r"\foo <x>", MathStyle::Display);
+        assert!(html.starts_with("<span class=\"math-error math-error-display\" role=\"alert\">"));
+        assert!(html.contains("<code>\\foo &lt;x&gt;</code>"));
+    }
+
+    #[test]
+    fn test_display_style_and_annotation() {
+        let html = latex_to_mathml("a < b", MathStyle::Display).unwrap();
+        assert!(html.starts_with("<math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\">"));
+        assert!(html.contains("<mo>&lt;</mo>"));
+        assert!(html.ends_with("<annotation encoding=\"application/x-tex\">a &lt; b</annotation></semantics></math>"));
+    }
+}
@@ -0,0 +1,283 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::Path;
+
+/// 解析日期字符串，支持 `YYYY-MM-DD` 和 `YYYY/MM/DD`
+pub fn parse_date(date_str: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(date_str, "%Y/%m/%d"))
+        .ok()
+}
+
+/// 自定义日期反序列化函数
+fn deserialize_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    // 如果解析失败，返回 None
+    Ok(s.as_deref().and_then(parse_date))
+}
+
+/// 解析日期时间字符串，支持 `YYYY-MM-DD HH:MM:SS`、`YYYY-MM-DDTHH:MM:SS` 和 `YYYY-MM-DD`
+pub fn parse_datetime(datetime_str: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(datetime_str, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(datetime_str, "%Y-%m-%dT%H:%M:%S"))
+        .ok()
+        // 如果只有日期，转换为日期时间（00:00:00）
+        .or_else(|| NaiveDate::parse_from_str(datetime_str, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))
+}
+
+/// 自定义日期时间反序列化函数
+fn deserialize_datetime<'de, D>(deserializer: D) -> Result<Option<NaiveDateTime>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    // 如果解析失败，返回 None
+    Ok(s.as_deref().and_then(parse_datetime))
+}
+
+/// 文章元数据结构，用于解析 Frontmatter
+///
+/// 同一结构也会被序列化进 `posts_index.json`，供运行时直接反序列化
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostMetadata {
+    /// 文章标题（缺省时为"未命名文章"）
+    #[serde(default = "default_title")]
+    pub title: String,
+    /// 发布日期（缺省时从文件名提取）
+    #[serde(default, deserialize_with = "deserialize_date")]
+    pub date: Option<NaiveDate>,
+    /// 作者名称
+    #[serde(default)]
+    pub author: Option<String>,
+    /// 标签列表
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// 分类列表
+    #[serde(default)]
+    pub categories: Option<Vec<String>>,
+    /// 文章摘要
+    #[serde(default)]
+    pub summary: Option<String>,
+    /// 封面图片 URL
+    #[serde(default)]
+    pub cover_image: Option<String>,
+    /// URL 友好标识符
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// 是否为草稿
+    #[serde(default = "default_false")]
+    pub draft: bool,
+    /// 最后更新时间
+    #[serde(default, deserialize_with = "deserialize_datetime")]
+    pub updated: Option<NaiveDateTime>,
+    /// 布局模板名称
+    #[serde(default)]
+    pub layout: Option<String>,
+    /// BibTeX 参考文献文件，相对于文章所在目录（仅构建时使用）
+    #[serde(default, skip_serializing)]
+    pub bibliography: Option<String>,
+    /// 行内引用的格式（仅构建时使用）
+    #[serde(default, skip_serializing)]
+    pub citation_style: CitationStyle,
+    /// 是否信任文章中的原始 HTML；为 true 时跳过白名单清理（仅构建时使用）
+    #[serde(default = "default_false", skip_serializing)]
+    pub trusted: bool,
+}
+
+/// 行内引用格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CitationStyle {
+    /// 按首次引用顺序编号：`[1]`
+    #[default]
+    Numeric,
+    /// 作者与年份：`(Knuth, 1984)`
+    AuthorYear,
+}
+
+fn default_false() -> bool {
+    false
+}
+
+fn default_title() -> String {
+    "未命名文章".to_string()
+}
+
+/// 页面包的入口文件名：`blogs/my-post/index.md` 与同目录的图片等资源组成一篇文章
+const BUNDLE_INDEX: &str = "index";
+
+/// 是否为页面包的入口文件（位于子目录中的 `index.md`）
+pub fn is_bundle_index(filename: &str) -> bool {
+    let path = Path::new(filename);
+    path.file_stem().and_then(|s| s.to_str()) == Some(BUNDLE_INDEX)
+        && path.parent().is_some_and(|dir| dir.file_name().is_some())
+}
+
+/// 用来生成 slug 和日期的名称：页面包取目录名，其余取不含扩展名的文件名
+fn post_stem(filename: &str) -> Option<&str> {
+    let path = Path::new(filename);
+    if is_bundle_index(filename) {
+        return path.parent()?.file_name()?.to_str();
+    }
+    path.file_stem()?.to_str()
+}
+
+/// 从文件名开头提取日期（格式：YYYY-MM-DD-xxx.md 或 YYYY-MM-DD-xxx/index.md）
+pub fn date_from_filename(filename: &str) -> Option<NaiveDate> {
+    let stem = post_stem(filename)?;
+    let date_str = stem.get(..10)?;
+    if !date_str.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        return None;
+    }
+    NaiveDate::parse_from_str(date_str, "%Y-%m-%d").ok()
+}
+
+/// 从文件名生成 slug
+///
+/// 移除 `YYYY-MM-DD-` 日期前缀，转换为小写并将空格、下划线替换为 `-`。
+/// build.rs 与运行时共用此函数，保证同一文件得到相同的 slug
+pub fn slug_from_filename(filename: &str) -> String {
+    let stem = post_stem(filename).unwrap_or(filename);
+
+    // 移除日期前缀（如果存在，格式：YYYY-MM-DD-）
+    let slug = match (date_from_filename(stem), stem.get(10..11), stem.get(11..)) {
+        (Some(_), Some("-"), Some(rest)) if !rest.is_empty() => rest,
+        _ => stem,
+    };
+
+    slugify(slug)
+}
+
+/// 将任意文本转换为 URL/文件名友好的形式
+///
+/// 转换为小写，空格和下划线替换为 `-`，去掉其他标点；中文等字母数字字符保留
+pub fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .replace([' ', '_'], "-")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-')
+        .collect()
+}
+
+impl PostMetadata {
+    /// 创建默认的 PostMetadata
+    pub fn new() -> Self {
+        Self {
+            title: default_title(),
+            date: None,
+            author: None,
+            tags: None,
+            categories: None,
+            summary: None,
+            cover_image: None,
+            slug: None,
+            draft: false,
+            updated: None,
+            layout: None,
+            bibliography: None,
+            citation_style: CitationStyle::Numeric,
+            trusted: false,
+        }
+    }
+
+    /// 从 YAML 字符串解析 Frontmatter
+    #[cfg(feature = "markdown")]
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+
+    /// 检查是否为草稿
+    pub fn is_draft(&self) -> bool {
+        self.draft
+    }
+
+    /// 从文件名生成 slug
+    /// 如果 metadata 中已有 slug，则使用已有的；否则从文件名生成
+    pub fn get_slug(&self, filename: &str) -> String {
+        if let Some(slug) = &self.slug {
+            return slug.clone();
+        }
+
+        slug_from_filename(filename)
+    }
+}
+
+impl Default for PostMetadata {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_from_yaml() {
+        let yaml = r#"
+title: "测试文章"
+date: 2025-11-23
+author: "测试作者"
+tags: ["Rust", "Dioxus"]
+draft: false
+"#;
+        let metadata = PostMetadata::from_yaml(yaml).unwrap();
+        assert_eq!(metadata.title, "测试文章");
+        assert_eq!(metadata.author, Some("测试作者".to_string()));
+        assert_eq!(metadata.tags, Some(vec!["Rust".to_string(), "Dioxus".to_string()]));
+        assert!(!metadata.is_draft());
+    }
+
+    #[test]
+    fn test_get_slug() {
+        let mut metadata = PostMetadata::new();
+        metadata.title = "测试文章".to_string();
+        
+        // 测试从文件名生成 slug
+        let slug = metadata.get_slug("2025-11-23-test-article.md");
+        assert_eq!(slug, "test-article");
+        
+        // 测试使用已有的 slug
+        metadata.slug = Some("custom-slug".to_string());
+        let slug = metadata.get_slug("2025-11-23-test-article.md");
+        assert_eq!(slug, "custom-slug");
+    }
+
+    #[test]
+    fn test_slug_from_filename() {
+        assert_eq!(slug_from_filename("2025-11-23-test-article.md"), "test-article");
+        assert_eq!(slug_from_filename("math-test.md"), "math-test");
+        assert_eq!(slug_from_filename("blogs/2025-11-23-Hello_World.md"), "hello-world");
+        // 只有日期的文件名保持原样
+        assert_eq!(slug_from_filename("2025-11-23.md"), "2025-11-23");
+        // 中文文件名不会因按字节切片而 panic
+        assert_eq!(slug_from_filename("测试文章.md"), "测试文章");
+        // 子目录中的文章和页面包
+        assert_eq!(slug_from_filename("2025/11/2025-11-23-nested.md"), "nested");
+        assert_eq!(slug_from_filename("2025-11-23-my-post/index.md"), "my-post");
+        assert_eq!(slug_from_filename("2025/my-post/index.md"), "my-post");
+        assert_eq!(slug_from_filename("index.md"), "index");
+    }
+
+    #[test]
+    fn test_date_from_filename() {
+        let date = date_from_filename("2025-11-23-test-article.md").unwrap();
+        assert_eq!(date.format("%Y-%m-%d").to_string(), "2025-11-23");
+        assert!(date_from_filename("math-test.md").is_none());
+        let date = date_from_filename("2025/2025-11-23-my-post/index.md").unwrap();
+        assert_eq!(date.format("%Y-%m-%d").to_string(), "2025-11-23");
+        assert!(date_from_filename("my-post/index.md").is_none());
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_missing_title_defaults() {
+        let metadata = PostMetadata::from_yaml("date: 2025-11-23").unwrap();
+        assert_eq!(metadata.title, "未命名文章");
+    }
+}
+
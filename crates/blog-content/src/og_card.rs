@@ -0,0 +1,250 @@
+use crate::feed::escape_xml;
+use crate::{Post, SiteConfig};
+
+/// 社交卡片尺寸（Open Graph 推荐的 1.91:1）
+pub const OG_CARD_WIDTH: u32 = 1200;
+pub const OG_CARD_HEIGHT: u32 = 630;
+
+/// 卡片左右留白
+const PADDING: f32 = 80.0;
+/// 标题字号
+const TITLE_FONT_SIZE: f32 = 64.0;
+/// 标题最多显示的行数
+const TITLE_MAX_LINES: usize = 3;
+/// 卡片使用的字体，优先常见的中文字体
+const FONT_FAMILY: &str =
+    "Noto Sans CJK SC, Source Han Sans SC, PingFang SC, Microsoft YaHei, WenQuanYi Micro Hei, DejaVu Sans, sans-serif";
+
+/// 字符的大致宽度（以字号为单位）：全角字符占 1，半角字符约占 0.55
+fn char_width(c: char) -> f32 {
+    if c.is_ascii() {
+        0.55
+    } else {
+        1.0
+    }
+}
+
+/// 按估算宽度把标题折成最多 `max_lines` 行，超出部分以省略号结尾
+///
+/// 英文按单词折行，中文可以在任意字符处折行
+fn wrap_title(title: &str, max_width: f32, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    let mut width = 0.0;
+    // 当前行最后一个可以断开的位置（字节偏移与该位置之前的宽度）
+    let mut last_break: Option<(usize, f32)> = None;
+
+    for c in title.split_whitespace().collect::<Vec<_>>().join(" ").chars() {
+        let w = char_width(c);
+        if width + w > max_width && !line.is_empty() {
+            let rest = match last_break {
+                Some((at, before)) if c.is_ascii_alphanumeric() => {
+                    let rest = line.split_off(at);
+                    width -= before;
+                    rest
+                }
+                _ => {
+                    width = 0.0;
+                    String::new()
+                }
+            };
+            lines.push(line.trim_end().to_string());
+            line = rest.trim_start().to_string();
+            last_break = None;
+            if c == ' ' {
+                continue;
+            }
+        }
+        if c == ' ' || !c.is_ascii() {
+            last_break = Some((line.len() + c.len_utf8(), width + w));
+        }
+        line.push(c);
+        width += w;
+    }
+    if !line.is_empty() {
+        lines.push(line.trim_end().to_string());
+    }
+
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        let last = lines.last_mut().unwrap();
+        let mut chars: Vec<char> = last.chars().collect();
+        while !chars.is_empty()
+            && chars.iter().map(|c| char_width(*c)).sum::<f32>() + 1.0 > max_width
+        {
+            chars.pop();
+        }
+        *last = chars.into_iter().collect::<String>() + "…";
+    }
+    lines
+}
+
+/// 根据文章生成社交卡片的 SVG：站点名、标题、日期和标签
+pub fn og_card_svg(post: &Post, site: &SiteConfig) -> String {
+    let metadata = &post.metadata;
+    let max_width = (OG_CARD_WIDTH as f32 - PADDING * 2.0) / TITLE_FONT_SIZE;
+    let title_lines = wrap_title(&metadata.title, max_width, TITLE_MAX_LINES);
+
+    let mut svg = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">
+<defs><linearGradient id="bg" x1="0" y1="0" x2="1" y2="1"><stop offset="0" stop-color="#1e3a8a"/><stop offset="1" stop-color="#2563eb"/></linearGradient></defs>
+<rect width="{w}" height="{h}" fill="url(#bg)"/>
+<rect x="{p}" y="150" width="96" height="6" rx="3" fill="#93c5fd"/>
+<text x="{p}" y="110" font-family="{font}" font-size="32" fill="#bfdbfe">{site}</text>
+"##,
+        w = OG_CARD_WIDTH,
+        h = OG_CARD_HEIGHT,
+        p = PADDING,
+        font = FONT_FAMILY,
+        site = escape_xml(&site.title),
+    );
+
+    for (i, line) in title_lines.iter().enumerate() {
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" font-family=\"{}\" font-size=\"{}\" font-weight=\"bold\" fill=\"#ffffff\">{}</text>\n",
+            PADDING,
+            250.0 + i as f32 * TITLE_FONT_SIZE * 1.3,
+            FONT_FAMILY,
+            TITLE_FONT_SIZE,
+            escape_xml(line)
+        ));
+    }
+
+    if let Some(date) = metadata.date {
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"530\" font-family=\"{}\" font-size=\"28\" fill=\"#dbeafe\">{}</text>\n",
+            PADDING,
+            FONT_FAMILY,
+            date.format("%Y-%m-%d")
+        ));
+    }
+
+    if let Some(tags) = metadata.tags.as_ref().filter(|tags| !tags.is_empty()) {
+        let tags = tags
+            .iter()
+            .map(|tag| format!("#{}", tag))
+            .collect::<Vec<_>>()
+            .join("  ");
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"580\" font-family=\"{}\" font-size=\"28\" fill=\"#93c5fd\">{}</text>\n",
+            PADDING,
+            FONT_FAMILY,
+            escape_xml(&tags)
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// 把卡片 SVG 栅格化为 PNG（纯 Rust 实现，不依赖外部程序）
+///
+/// 加载系统字体较慢，构建时应只创建一个实例并复用
+#[cfg(feature = "og-image")]
+pub struct OgCardRenderer {
+    options: resvg::usvg::Options<'static>,
+}
+
+#[cfg(feature = "og-image")]
+impl OgCardRenderer {
+    /// 加载系统字体以及 `font_dirs` 中的字体文件
+    pub fn new(font_dirs: &[&std::path::Path]) -> Self {
+        let mut options = resvg::usvg::Options::default();
+        let fontdb = options.fontdb_mut();
+        fontdb.load_system_fonts();
+        for dir in font_dirs {
+            fontdb.load_fonts_dir(dir);
+        }
+
+        // fontdb 默认把 sans-serif 映射为 Arial；改为实际安装的字体，
+        // 优先卡片中列出的中文字体，保证任何环境下文字都能渲染出来
+        let installed = |family: &str| {
+            fontdb
+                .faces()
+                .any(|face| face.families.iter().any(|(name, _)| name == family))
+        };
+        let fallback = FONT_FAMILY
+            .split(',')
+            .map(str::trim)
+            .find(|family| *family != "sans-serif" && installed(family))
+            .map(str::to_string)
+            .or_else(|| {
+                fontdb
+                    .faces()
+                    .find_map(|face| face.families.first().map(|(name, _)| name.clone()))
+            });
+        if let Some(family) = fallback {
+            fontdb.set_sans_serif_family(family);
+        }
+        Self { options }
+    }
+
+    /// 渲染 SVG 并编码为 PNG
+    pub fn render_png(&self, svg: &str) -> anyhow::Result<Vec<u8>> {
+        use anyhow::Context;
+        use resvg::tiny_skia::{Pixmap, Transform};
+
+        let tree = resvg::usvg::Tree::from_str(svg, &self.options).context("解析卡片 SVG 失败")?;
+        let mut pixmap =
+            Pixmap::new(OG_CARD_WIDTH, OG_CARD_HEIGHT).context("无法创建卡片画布")?;
+        resvg::render(&tree, Transform::default(), &mut pixmap.as_mut());
+        pixmap.encode_png().context("编码 PNG 失败")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PostMetadata;
+    use chrono::NaiveDate;
+
+    fn post(title: &str) -> Post {
+        Post {
+            metadata: PostMetadata {
+                title: title.to_string(),
+                date: NaiveDate::from_ymd_opt(2025, 11, 23),
+                tags: Some(vec!["Rust".to_string(), "博客".to_string()]),
+                ..PostMetadata::default()
+            },
+            file_path: "2025-11-23-hello.md".to_string(),
+            content: String::new(),
+            html_content: String::new(),
+            ..Post::default()
+        }
+    }
+
+    #[test]
+    fn test_wrap_title() {
+        assert_eq!(wrap_title("短标题", 16.0, 3), vec!["短标题"]);
+        // 英文按单词折行
+        assert_eq!(
+            wrap_title("hello world again", 4.0, 3),
+            vec!["hello", "world", "again"]
+        );
+        // 中文按字符折行，超出行数时以省略号结尾
+        let lines = wrap_title(&"测".repeat(40), 10.0, 2);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "测".repeat(10));
+        assert!(lines[1].ends_with('…'));
+        assert!(lines[1].chars().count() <= 10);
+    }
+
+    #[test]
+    fn test_og_card_svg() {
+        let svg = og_card_svg(&post("A & B <测试>"), &SiteConfig::default());
+        assert!(svg.contains("A &amp; B &lt;测试&gt;"));
+        assert!(svg.contains("2025-11-23"));
+        assert!(svg.contains("#Rust  #博客"));
+        assert!(svg.contains(&SiteConfig::default().title));
+    }
+
+    #[cfg(feature = "og-image")]
+    #[test]
+    fn test_render_png() {
+        let renderer = OgCardRenderer::new(&[]);
+        let png = renderer
+            .render_png(&og_card_svg(&post("Hello"), &SiteConfig::default()))
+            .unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::feed::escape_xml;
+use crate::text::html_to_text;
+use crate::PostMetadata;
+use serde::{Deserialize, Serialize};
+
+/// 文章结构，包含元数据和内容
+///
+/// 序列化进 `posts_index.json` 时只保留元数据，正文字段为空
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Post {
+    /// 文章元数据
+    #[serde(flatten)]
+    pub metadata: PostMetadata,
+    /// 相对于 blogs 目录的源文件路径
+    #[serde(default)]
+    pub file_path: String,
+    /// Markdown 原始内容
+    #[serde(default, skip_serializing)]
+    pub content: String,
+    /// 正文第一行在源文件中的行号，用于报告问题所在的行（仅构建时使用）
+    #[serde(default, skip_serializing)]
+    pub content_line: usize,
+    /// 渲染后的 HTML 内容
+    #[serde(default, skip_serializing)]
+    pub html_content: String,
+    /// 目录：正文中按顺序出现的所有标题
+    #[serde(default)]
+    pub toc: Vec<TocEntry>,
+    /// 正文字数：中日韩文字按字计，其他语言按单词计，不含代码块和公式
+    #[serde(default)]
+    pub word_count: usize,
+    /// 预计阅读时间（分钟）
+    #[serde(default)]
+    pub reading_time: usize,
+    /// 构建时生成的摘录 HTML，见 [`Post::excerpt`]
+    #[serde(default)]
+    pub excerpt_html: String,
+    /// 页面包中的资源文件，相对于 `index.md` 所在目录，由构建脚本复制到
+    /// [`post_asset_path`](crate::site::post_asset_path)（仅构建时使用）
+    #[serde(default, skip_serializing)]
+    pub assets: Vec<String>,
+    /// 渲染时发现的问题（如未定义的公式标签），由构建脚本输出为警告
+    #[serde(default, skip_serializing)]
+    pub warnings: Vec<String>,
+}
+
+/// 目录中的一项，对应正文中的一个标题
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TocEntry {
+    /// 标题级别（1–6）
+    pub level: u8,
+    /// 标题的 `id`，可通过 `#id` 链接到该节
+    pub id: String,
+    /// 标题纯文本
+    pub title: String,
+}
+
+impl Post {
+    /// 文章的 slug（frontmatter 优先，否则由文件名生成）
+    pub fn slug(&self) -> String {
+        self.metadata.get_slug(&self.file_path)
+    }
+
+    /// 文章摘录（HTML）：依次取 frontmatter 的 `summary`、`<!-- more -->` 之前的内容、
+    /// 正文开头的若干字
+    pub fn excerpt(&self) -> String {
+        if !self.excerpt_html.is_empty() {
+            return self.excerpt_html.clone();
+        }
+        self.metadata
+            .summary
+            .as_deref()
+            .map(escape_xml)
+            .unwrap_or_default()
+    }
+
+    /// 摘录的纯文本，用于页面描述和订阅源摘要
+    pub fn excerpt_text(&self) -> String {
+        html_to_text(&self.excerpt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_excerpt_falls_back_to_summary() {
+        let mut post = Post::default();
+        assert_eq!(post.excerpt(), "");
+
+        post.metadata.summary = Some("A & B".to_string());
+        assert_eq!(post.excerpt(), "A &amp; B");
+        assert_eq!(post.excerpt_text(), "A & B");
+
+        post.excerpt_html = "<p>摘录</p>".to_string();
+        assert_eq!(post.excerpt(), "<p>摘录</p>");
+        assert_eq!(post.excerpt_text(), "摘录");
+    }
+}
@@ -0,0 +1,205 @@
+use crate::feed::escape_xml;
+use crate::site::{decode_path_segment, is_safe_path_segment};
+use crate::sitemap::sitemap_entries;
+use crate::{PostIndex, SiteConfig};
+use anyhow::{bail, Result};
+use std::path::PathBuf;
+
+/// 应用挂载点，与 dx 生成的 `index.html` 保持一致
+pub const MOUNT_POINT: &str = r#"<div id="main"></div>"#;
+
+/// 找不到 dx 生成的 `index.html` 时使用的最简页面外壳（不加载 WASM）
+pub const FALLBACK_SHELL: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+</head>
+<body>
+<div id="main"></div>
+</body>
+</html>
+"#;
+
+/// 需要静态预渲染的所有路径：站点地图中的页面外加 404 页面
+pub fn prerender_paths(index: &PostIndex, site: &SiteConfig) -> Vec<String> {
+    let mut paths: Vec<String> = sitemap_entries(index, site)
+        .into_iter()
+        .map(|entry| entry.path)
+        .collect();
+    paths.push("/404".to_string());
+    paths
+}
+
+/// 路径对应的输出文件（相对于输出目录）：`/blog/rust` → `blog/rust/index.html`
+///
+/// 路径段会先做百分号解码，静态文件服务器按解码后的路径查找文件；
+/// 解码后为 `.`、`..` 或含有 `/`、`\` 的路径段会写到别的目录，返回错误
+pub fn output_file(path: &str) -> Result<PathBuf> {
+    let mut file = PathBuf::new();
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        let decoded = decode_path_segment(segment);
+        if !is_safe_path_segment(&decoded) {
+            bail!("路径 {} 中的 {:?} 不能用作目录名", path, decoded);
+        }
+        file.push(decoded);
+    }
+    file.push("index.html");
+    Ok(file)
+}
+
+/// 把预渲染的正文放进页面外壳的挂载点
+pub fn inject_into_shell(shell: &str, body_html: &str) -> Result<String> {
+    if !shell.contains(MOUNT_POINT) {
+        bail!("页面外壳中找不到挂载点 {}", MOUNT_POINT);
+    }
+    Ok(shell.replacen(
+        MOUNT_POINT,
+        &format!(r#"<div id="main">{}</div>"#, body_html),
+        1,
+    ))
+}
+
+/// 页面引用的样式表和脚本，地址应为 `asset!()` 解析后的带哈希路径
+///
+/// SSR 不会输出 `document::Stylesheet` 等头部元素，需要随页面元数据一起写入 `<head>`
+pub fn asset_head_html(stylesheets: &[String], scripts: &[String]) -> String {
+    let mut html = String::new();
+    for href in stylesheets {
+        html.push_str(&format!("<link rel=\"stylesheet\" href=\"{}\">\n", escape_xml(href)));
+    }
+    for src in scripts {
+        html.push_str(&format!("<script src=\"{}\"></script>\n", escape_xml(src)));
+    }
+    html
+}
+
+/// 把页面的 `<head>` 内容写入页面外壳，替换外壳中原有的 `<title>`
+pub fn inject_head(shell: &str, head_html: &str) -> Result<String> {
+    let mut html = shell.to_string();
+    if let (Some(start), Some(end)) = (html.find("<title>"), html.find("</title>")) {
+        if start < end {
+            html.replace_range(start..end + "</title>".len(), "");
+        }
+    }
+    let Some(head_end) = html.find("</head>") else {
+        bail!("页面外壳中找不到 </head>");
+    };
+    html.insert_str(head_end, head_html);
+    Ok(html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Post, PostMetadata};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_output_file() {
+        assert_eq!(output_file("/").unwrap(), PathBuf::from("index.html"));
+        assert_eq!(output_file("/blog/page/2").unwrap(), PathBuf::from("blog/page/2/index.html"));
+        assert_eq!(
+            output_file("/blog/tag/%E5%8D%9A%E5%AE%A2").unwrap(),
+            PathBuf::from("blog/tag/博客/index.html")
+        );
+    }
+
+    #[test]
+    fn test_unsafe_taxonomy_names_never_leave_output_dir() {
+        let post = Post {
+            metadata: PostMetadata {
+                title: "A".to_string(),
+                tags: Some(vec!["../x".to_string(), "a/b".to_string()]),
+                categories: Some(vec!["..".to_string(), "/etc".to_string()]),
+                ..PostMetadata::default()
+            },
+            file_path: "2025-01-01-a.md".to_string(),
+            ..Post::default()
+        };
+        assert!(PostIndex::from_posts(vec![post.clone()]).is_err());
+
+        // 绕过索引校验时，输出路径同样会被拒绝
+        let mut index = PostIndex::new();
+        index.posts.push(post);
+        index.sorted_by_date.push(0);
+        for name in ["../x", "a/b"] {
+            index.tags.insert(name.to_string(), vec![0]);
+        }
+        for name in ["..", "/etc"] {
+            index.categories.insert(name.to_string(), vec![0]);
+        }
+        let paths = prerender_paths(&index, &SiteConfig::default());
+        let unsafe_paths: Vec<&String> = paths
+            .iter()
+            .filter(|path| path.starts_with("/blog/tag/") || path.starts_with("/blog/category/"))
+            .collect();
+        assert_eq!(unsafe_paths.len(), 4, "{:?}", paths);
+        for path in unsafe_paths {
+            assert!(output_file(path).is_err(), "{}", path);
+        }
+        assert!(output_file("/blog/a").is_ok());
+    }
+
+    #[test]
+    fn test_prerender_paths_cover_every_route() {
+        let post = Post {
+            metadata: PostMetadata {
+                title: "A".to_string(),
+                date: NaiveDate::from_ymd_opt(2025, 1, 1),
+                tags: Some(vec!["Rust".to_string()]),
+                categories: Some(vec!["技术".to_string()]),
+                ..PostMetadata::default()
+            },
+            file_path: "2025-01-01-a.md".to_string(),
+            content: String::new(),
+            html_content: String::new(),
+            ..Post::default()
+        };
+        let index = PostIndex::from_posts(vec![post]).unwrap();
+        let paths = prerender_paths(&index, &SiteConfig::default());
+
+        for expected in [
+            "/",
+            "/blog",
+            "/blog/tags",
+            "/blog/categories",
+            "/blog/a",
+            "/blog/tag/Rust",
+            "/blog/category/%E6%8A%80%E6%9C%AF",
+            "/404",
+        ] {
+            assert!(paths.iter().any(|p| p == expected), "缺少 {}", expected);
+        }
+    }
+
+    #[test]
+    fn test_inject_into_shell() {
+        let html = inject_into_shell(FALLBACK_SHELL, "<h1>hi</h1>").unwrap();
+        assert!(html.contains(r#"<div id="main"><h1>hi</h1></div>"#));
+        assert!(inject_into_shell("<body></body>", "x").is_err());
+    }
+
+    #[test]
+    fn test_asset_head_html() {
+        let html = asset_head_html(
+            &["/assets/main-dxh12ab.css".to_string()],
+            &["/assets/theme-dxh34cd.js".to_string()],
+        );
+        assert_eq!(
+            html,
+            "<link rel=\"stylesheet\" href=\"/assets/main-dxh12ab.css\">\n<script src=\"/assets/theme-dxh34cd.js\"></script>\n"
+        );
+    }
+
+    #[test]
+    fn test_inject_head_replaces_title() {
+        let shell = "<html><head><title>zhengbi_yong_homepage</title></head><body></body></html>";
+        let html = inject_head(shell, "<title>文章 - 我的博客</title>\n").unwrap();
+        assert_eq!(
+            html,
+            "<html><head><title>文章 - 我的博客</title>\n</head><body></body></html>"
+        );
+        assert!(inject_head("<body></body>", "").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+//! 渲染结果的 HTML 白名单清理
+//!
+//! pulldown-cmark 会原样输出文章中的 HTML，而应用直接把 `html_content` 注入页面，
+//! 所以构建时在这里去掉脚本、事件处理属性和危险链接，只保留 Markdown、代码高亮、
+//! 公式（MathML）与文献引用会生成的标记。Frontmatter 中 `trusted: true` 的文章跳过这一步。
+
+use ammonia::Builder;
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// 公式渲染输出的 MathML 元素及其属性
+const MATHML_TAGS: &[(&str, &[&str])] = &[
+    ("math", &["xmlns", "display"]),
+    ("semantics", &[]),
+    ("annotation", &["encoding"]),
+    ("mrow", &["href"]),
+    ("mi", &["mathvariant"]),
+    ("mn", &[]),
+    ("mo", &["stretchy", "fence", "form", "largeop", "movablelimits", "symmetric", "minsize", "maxsize"]),
+    ("mtext", &[]),
+    ("mspace", &["width"]),
+    ("mfrac", &["linethickness"]),
+    ("msqrt", &[]),
+    ("mroot", &[]),
+    ("msub", &[]),
+    ("msup", &[]),
+    ("msubsup", &[]),
+    ("munder", &["accentunder"]),
+    ("mover", &["accent"]),
+    ("munderover", &[]),
+    ("mtable", &["columnalign", "columnspacing"]),
+    ("mtr", &[]),
+    ("mtd", &[]),
+    ("mstyle", &["displaystyle"]),
+];
+
+/// 页面布局使用的元素 id（含懒加载图片的 `lazy-img-N`），文章中的元素不能占用
+const LAYOUT_IDS: &[&str] = &["main", "article-content", "theme-toggle-btn"];
+
+/// `id` 是否与页面布局中的元素冲突
+pub(crate) fn is_layout_id(id: &str) -> bool {
+    LAYOUT_IDS.contains(&id) || id.starts_with("lazy-img-")
+}
+
+/// 渲染器生成的公式锚点 `eq-N`
+fn is_equation_id(id: &str) -> bool {
+    id.strip_prefix("eq-")
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn builder() -> &'static Builder<'static> {
+    static BUILDER: OnceLock<Builder<'static>> = OnceLock::new();
+    BUILDER.get_or_init(|| {
+        let mut builder = Builder::default();
+        builder
+            // 引用列表、脚注等使用 <section>；任务列表使用复选框
+            .add_tags(["section", "input"])
+            .add_tags(MATHML_TAGS.iter().map(|(tag, _)| *tag))
+            // 代码高亮、公式编号和引用都依赖 class
+            .add_generic_attributes(["class", "aria-hidden", "role"])
+            // id 只保留在渲染器会生成 id 的元素上：标题、公式、脚注和参考文献条目
+            .add_tag_attributes("h1", ["id"])
+            .add_tag_attributes("h2", ["id"])
+            .add_tag_attributes("h3", ["id"])
+            .add_tag_attributes("h4", ["id"])
+            .add_tag_attributes("h5", ["id"])
+            .add_tag_attributes("h6", ["id"])
+            .add_tag_attributes("span", ["id"])
+            .add_tag_attributes("div", ["id"])
+            .add_tag_attributes("li", ["id"])
+            .add_tag_attributes("input", ["type", "checked", "disabled"])
+            // 表格列对齐以 style 输出，只保留 text-align
+            .add_tag_attributes("th", ["style"])
+            .add_tag_attributes("td", ["style"])
+            .filter_style_properties(HashSet::from(["text-align"]))
+            .attribute_filter(|element, attribute, value| match (element, attribute) {
+                ("input", "type") if value != "checkbox" => None,
+                (_, "id") if is_layout_id(value) => None,
+                ("span", "id") if !is_equation_id(value) => None,
+                ("li", "id") if !value.starts_with("ref-") => None,
+                _ => Some(value.into()),
+            })
+            // 站内锚点不需要 rel="noopener noreferrer"
+            .link_rel(None);
+        for (tag, attributes) in MATHML_TAGS {
+            builder.add_tag_attributes(tag, attributes.iter().copied());
+        }
+        builder
+    })
+}
+
+/// 按白名单清理 HTML，去掉脚本、事件处理属性、`javascript:` 链接和未知元素
+pub fn sanitize_html(html: &str) -> String {
+    builder().clean(html).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_removes_script_elements() {
+        let html = sanitize_html("<p>正文</p><script>alert(1)</script><SCRIPT src=\"x.js\"></SCRIPT>");
+        assert_eq!(html, "<p>正文</p>");
+    }
+
+    #[test]
+    fn test_removes_event_handlers_and_style() {
+        let html = sanitize_html(
+            "<img src=\"a.png\" onerror=\"alert(1)\"><p onclick=\"alert(1)\" style=\"position:fixed\">x</p>",
+        );
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("onclick"));
+        assert!(!html.contains("position"));
+        assert!(html.contains("<img src=\"a.png\">"));
+    }
+
+    #[test]
+    fn test_removes_dangerous_urls() {
+        for url in ["javascript:alert(1)", "JaVaScRiPt:alert(1)", "data:text/html,<script>alert(1)</script>", "vbscript:x"] {
+            let html = sanitize_html(&format!("<a href=\"{}\">x</a>", url));
+            assert_eq!(html, "<a>x</a>", "{}", url);
+        }
+        let html = sanitize_html("<math><mrow href=\"javascript:alert(1)\"><mi>x</mi></mrow></math>");
+        assert!(!html.contains("javascript"));
+    }
+
+    #[test]
+    fn test_removes_embedding_elements() {
+        let html = sanitize_html(concat!(
+            "<iframe src=\"https://evil.example\"></iframe>",
+            "<svg onload=\"alert(1)\"><circle r=\"1\"/></svg>",
+            "<object data=\"x.swf\"></object><embed src=\"x.swf\">",
+            "<form action=\"https://evil.example\"><input type=\"text\" name=\"p\"></form>",
+            "<style>body{display:none}</style>",
+            "<meta http-equiv=\"refresh\" content=\"0;url=https://evil.example\">",
+        ));
+        for needle in ["iframe", "svg", "onload", "object", "embed", "form", "text", "style", "meta", "evil"] {
+            assert!(!html.contains(needle), "{} 未被清理: {}", needle, html);
+        }
+    }
+
+    #[test]
+    fn test_mathml_cannot_smuggle_script() {
+        let html = sanitize_html("<math><mtext><table><mglyph><style><img src=x onerror=alert(1)></style></mglyph></table></mtext></math>");
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("<style"));
+    }
+
+    #[test]
+    fn test_restricts_ids() {
+        let cases = [
+            ("<p id=\"x\">a</p>", "<p>a</p>"),
+            ("<a id=\"x\" href=\"#y\">a</a>", "<a href=\"#y\">a</a>"),
+            ("<h2 id=\"main\">a</h2>", "<h2>a</h2>"),
+            ("<div id=\"article-content\">a</div>", "<div>a</div>"),
+            ("<img id=\"lazy-img-0\" src=\"a.png\">", "<img src=\"a.png\">"),
+            ("<span id=\"eq-x\">a</span>", "<span>a</span>"),
+            ("<li id=\"x\">a</li>", "<li>a</li>"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(sanitize_html(input), expected, "{}", input);
+        }
+        let kept = [
+            "<span class=\"equation\" id=\"eq-12\">x</span>",
+            "<div class=\"footnote-definition\" id=\"note\"><sup class=\"footnote-definition-label\">1</sup></div>",
+        ];
+        for input in kept {
+            assert_eq!(sanitize_html(input), input);
+        }
+    }
+
+    #[test]
+    fn test_keeps_generated_markup() {
+        let inputs = [
+            "<h2 id=\"标题\"><a class=\"heading-anchor\" href=\"#标题\" aria-hidden=\"true\">#</a>标题</h2>",
+            "<pre class=\"code-block hl-code\"><code class=\"language-rust\"><span class=\"hl-keyword\">fn</span></code></pre>",
+            "<span class=\"citation\">[<a href=\"#ref-knuth1984\">1</a>]</span>",
+            "<section class=\"references\"><ol class=\"references-list\"><li id=\"ref-knuth1984\">Knuth</li></ol></section>",
+            "<span class=\"math-error\" role=\"alert\"><code>\\frac</code></span>",
+            "<table><thead><tr><th style=\"text-align:center\">a</th></tr></thead></table>",
+            "<ul><li><input disabled=\"\" type=\"checkbox\" checked=\"\">完成</li></ul>",
+            "<a href=\"https://example.com/\">外链</a>",
+        ];
+        for input in inputs {
+            assert_eq!(sanitize_html(input), input);
+        }
+    }
+
+    #[test]
+    fn test_keeps_mathml() {
+        let mathml = crate::math::render_math(r"\sum_{i=1}^n \frac{a_i}{\sqrt{x}}", crate::math::MathStyle::Display);
+        assert_eq!(sanitize_html(&mathml), mathml);
+    }
+
+    #[test]
+    fn test_drops_unsupported_style_properties() {
+        let html = sanitize_html("<td style=\"text-align: left; background: url(x)\">a</td>");
+        assert!(!html.contains("background"));
+    }
+}
@@ -0,0 +1,387 @@
+use crate::text::{html_to_text, is_cjk};
+use crate::PostIndex;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// 各字段中词项的权重
+const TITLE_WEIGHT: u32 = 10;
+const TAG_WEIGHT: u32 = 5;
+const SUMMARY_WEIGHT: u32 = 3;
+const BODY_WEIGHT: u32 = 1;
+/// 中日韩单字词项的权重为所在字段权重的几分之一
+const CJK_UNIGRAM_DIVISOR: u32 = 2;
+
+/// 把文本切分为检索词项
+///
+/// 英文和数字按单词切分并转为小写；中日韩文字没有空格，按相邻两字（bigram）切分，
+/// 单独出现的一个字保留为单字词项
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut cjk_run: Vec<char> = Vec::new();
+
+    let flush_word = |word: &mut String, tokens: &mut Vec<String>| {
+        if !word.is_empty() {
+            tokens.push(std::mem::take(word));
+        }
+    };
+    let flush_cjk = |run: &mut Vec<char>, tokens: &mut Vec<String>| {
+        match run.len() {
+            0 => {}
+            1 => tokens.push(run[0].to_string()),
+            _ => tokens.extend(run.windows(2).map(|pair| pair.iter().collect())),
+        }
+        run.clear();
+    };
+
+    for c in text.chars() {
+        if is_cjk(c) {
+            flush_word(&mut word, &mut tokens);
+            cjk_run.push(c);
+        } else if c.is_alphanumeric() {
+            flush_cjk(&mut cjk_run, &mut tokens);
+            word.extend(c.to_lowercase());
+        } else {
+            flush_word(&mut word, &mut tokens);
+            flush_cjk(&mut cjk_run, &mut tokens);
+        }
+    }
+    flush_word(&mut word, &mut tokens);
+    flush_cjk(&mut cjk_run, &mut tokens);
+    tokens
+}
+
+/// 索引中的一篇文章
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchDoc {
+    /// 文章 slug
+    pub slug: String,
+    /// 文章标题
+    pub title: String,
+    /// 发布日期（`YYYY-MM-DD`）
+    #[serde(default)]
+    pub date: Option<String>,
+    /// 文章摘要，与正文一起用于生成摘录；正文不重复存入索引
+    #[serde(default)]
+    pub summary: String,
+}
+
+impl SearchDoc {
+    /// 用于生成摘录的纯文本：摘要加上由预渲染正文 HTML 转换的纯文本
+    pub fn text(&self, body_html: &str) -> String {
+        let body = html_to_text(body_html);
+        if self.summary.is_empty() {
+            body
+        } else {
+            format!("{} {}", self.summary, body)
+        }
+    }
+}
+
+/// 一条搜索结果
+#[derive(Debug, Clone, Copy)]
+pub struct SearchHit<'a> {
+    /// 命中的文章
+    pub doc: &'a SearchDoc,
+    /// 相关度得分
+    pub score: u32,
+}
+
+/// 高亮文本中的一段
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextPart {
+    /// 文本内容
+    pub text: String,
+    /// 是否为命中的关键词
+    pub highlight: bool,
+}
+
+/// 倒排索引，由 build.rs 生成，运行时在 WASM 中本地检索
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchIndex {
+    /// 所有文章
+    pub docs: Vec<SearchDoc>,
+    /// 词项到 `(文章序号, 权重)` 列表的映射
+    pub terms: BTreeMap<String, Vec<(u32, u32)>>,
+}
+
+impl SearchIndex {
+    /// 根据文章索引构建：标题、标签、摘要和正文分别加权
+    ///
+    /// 连续的中日韩文字除了相邻两字，还以较低的权重索引每个单字，
+    /// 否则只输入一个字时找不到位于词尾的字（如“个人博客”中的“客”）
+    ///
+    /// 需要文章带有渲染后的 `html_content`，因此应在构建时调用
+    pub fn build(index: &PostIndex) -> Self {
+        let mut search = SearchIndex::default();
+        for post in index.get_recent_posts(index.posts.len()) {
+            let doc_id = search.docs.len() as u32;
+            let metadata = &post.metadata;
+            let summary = metadata.summary.clone().unwrap_or_default();
+            let body = html_to_text(&post.html_content);
+
+            let mut weights: HashMap<String, u32> = HashMap::new();
+            let mut add = |text: &str, weight: u32| {
+                for token in tokenize(text) {
+                    *weights.entry(token).or_default() += weight;
+                }
+                let unigram_weight = (weight / CJK_UNIGRAM_DIVISOR).max(1);
+                for c in text.chars().filter(|c| is_cjk(*c)) {
+                    *weights.entry(c.to_string()).or_default() += unigram_weight;
+                }
+            };
+            add(&metadata.title, TITLE_WEIGHT);
+            for tag in metadata.tags.iter().flatten() {
+                add(tag, TAG_WEIGHT);
+            }
+            add(&summary, SUMMARY_WEIGHT);
+            add(&body, BODY_WEIGHT);
+
+            for (token, weight) in weights {
+                search.terms.entry(token).or_default().push((doc_id, weight));
+            }
+            search.docs.push(SearchDoc {
+                slug: post.slug(),
+                title: metadata.title.clone(),
+                date: metadata.date.map(|date| date.format("%Y-%m-%d").to_string()),
+                summary,
+            });
+        }
+        search
+    }
+
+    /// 从 JSON 解析
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).context("解析搜索索引失败")
+    }
+
+    /// 序列化为紧凑的 JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).context("序列化搜索索引失败")
+    }
+
+    /// 检索：每个查询词项都必须命中，按得分从高到低排序
+    ///
+    /// 查询词项按前缀匹配，因此输入单个汉字或不完整的单词也能找到结果
+    pub fn search(&self, query: &str) -> Vec<SearchHit<'_>> {
+        let tokens = tokenize(query);
+        if tokens.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scores: Option<HashMap<u32, u32>> = None;
+        for token in &tokens {
+            let mut matched: HashMap<u32, u32> = HashMap::new();
+            for (_, postings) in self
+                .terms
+                .range(token.clone()..)
+                .take_while(|(term, _)| term.starts_with(token.as_str()))
+            {
+                for &(doc, weight) in postings {
+                    let score = matched.entry(doc).or_default();
+                    *score = (*score).max(weight);
+                }
+            }
+            scores = Some(match scores {
+                None => matched,
+                Some(previous) => previous
+                    .into_iter()
+                    .filter_map(|(doc, score)| matched.get(&doc).map(|m| (doc, score + m)))
+                    .collect(),
+            });
+        }
+
+        let mut ranked: Vec<(u32, u32)> = scores.unwrap_or_default().into_iter().collect();
+        // 得分相同时保持文章的日期顺序（docs 按从新到旧排列）
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked
+            .into_iter()
+            .filter_map(|(doc, score)| {
+                self.docs.get(doc as usize).map(|doc| SearchHit { doc, score })
+            })
+            .collect()
+    }
+}
+
+/// 标出文本中所有命中查询的片段（不区分大小写）
+pub fn highlight(text: &str, query: &str) -> Vec<TextPart> {
+    let chars: Vec<char> = text.chars().collect();
+    let marks = match_marks(&chars, &tokenize(query));
+    split_parts(&chars, &marks)
+}
+
+/// 截取第一个命中位置附近约 `length` 个字符的摘录并高亮关键词
+///
+/// 没有命中时返回开头的一段
+pub fn snippet(text: &str, query: &str, length: usize) -> Vec<TextPart> {
+    let chars: Vec<char> = text.chars().collect();
+    let marks = match_marks(&chars, &tokenize(query));
+    let first = marks.iter().position(|marked| *marked).unwrap_or(0);
+    let start = first.saturating_sub(length / 4);
+    let end = (start + length).min(chars.len());
+
+    let mut parts = split_parts(&chars[start..end], &marks[start..end]);
+    if start > 0 {
+        parts.insert(0, TextPart { text: "…".to_string(), highlight: false });
+    }
+    if end < chars.len() {
+        parts.push(TextPart { text: "…".to_string(), highlight: false });
+    }
+    parts
+}
+
+/// 标记每个字符是否属于某个命中的关键词
+fn match_marks(chars: &[char], terms: &[String]) -> Vec<bool> {
+    // 与 tokenize 一样按 Unicode 规则转小写；个别字符转换后不止一个字符，记录每个字符的来源
+    let (lower, origin): (Vec<char>, Vec<usize>) = chars
+        .iter()
+        .enumerate()
+        .flat_map(|(i, c)| c.to_lowercase().map(move |lower| (lower, i)))
+        .unzip();
+    let mut marks = vec![false; chars.len()];
+    for term in terms {
+        let term: Vec<char> = term.chars().collect();
+        if term.is_empty() || term.len() > lower.len() {
+            continue;
+        }
+        for i in 0..=lower.len() - term.len() {
+            if lower[i..i + term.len()] == term[..] {
+                origin[i..i + term.len()].iter().for_each(|&j| marks[j] = true);
+            }
+        }
+    }
+    marks
+}
+
+/// 按标记把字符合并为连续的高亮/普通片段
+fn split_parts(chars: &[char], marks: &[bool]) -> Vec<TextPart> {
+    let mut parts: Vec<TextPart> = Vec::new();
+    for (c, highlight) in chars.iter().zip(marks) {
+        match parts.last_mut() {
+            Some(part) if part.highlight == *highlight => part.text.push(*c),
+            _ => parts.push(TextPart { text: c.to_string(), highlight: *highlight }),
+        }
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Post, PostMetadata};
+    use chrono::NaiveDate;
+
+    fn post(slug: &str, title: &str, tags: &[&str], body: &str, day: u32) -> Post {
+        Post {
+            metadata: PostMetadata {
+                title: title.to_string(),
+                date: NaiveDate::from_ymd_opt(2025, 1, day),
+                tags: Some(tags.iter().map(|t| t.to_string()).collect()),
+                slug: Some(slug.to_string()),
+                ..PostMetadata::default()
+            },
+            file_path: format!("{}.md", slug),
+            content: String::new(),
+            html_content: body.to_string(),
+            ..Post::default()
+        }
+    }
+
+    fn sample_index() -> SearchIndex {
+        let index = PostIndex::from_posts(vec![
+            post("dioxus", "Dioxus 入门", &["Rust"], "<p>用 Rust 构建个人博客</p>", 1),
+            post("math", "数学公式", &["LaTeX"], "<p>行内公式与块级公式</p>", 2),
+        ])
+        .unwrap();
+        SearchIndex::build(&index)
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(tokenize("Hello, World!"), vec!["hello", "world"]);
+        assert_eq!(tokenize("个人博客"), vec!["个人", "人博", "博客"]);
+        assert_eq!(tokenize("Dioxus博客 v0.7"), vec!["dioxus", "博客", "v0", "7"]);
+        assert_eq!(tokenize("用 Rust"), vec!["用", "rust"]);
+    }
+
+    #[test]
+    fn test_search() {
+        let index = sample_index();
+
+        let hits = index.search("博客");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].doc.slug, "dioxus");
+
+        // 所有词项都必须命中
+        assert_eq!(index.search("rust 公式").len(), 0);
+        // 英文不区分大小写，支持前缀
+        assert_eq!(index.search("DIOX")[0].doc.slug, "dioxus");
+        // 单个汉字按前缀匹配
+        assert_eq!(index.search("式")[0].doc.slug, "math");
+        // 词尾的单字也能找到
+        assert_eq!(index.search("客")[0].doc.slug, "dioxus");
+        assert_eq!(index.search("客").len(), 1);
+        assert!(index.search("  ").is_empty());
+    }
+
+    #[test]
+    fn test_search_ranks_title_above_body() {
+        let index = PostIndex::from_posts(vec![
+            post("a", "其他", &[], "<p>正文提到 Rust</p>", 1),
+            post("b", "Rust 指南", &[], "<p>内容</p>", 2),
+        ])
+        .unwrap();
+        let search = SearchIndex::build(&index);
+        let slugs: Vec<_> = search.search("rust").iter().map(|hit| hit.doc.slug.clone()).collect();
+        assert_eq!(slugs, vec!["b", "a"]);
+    }
+
+    #[test]
+    fn test_index_does_not_store_body() {
+        let index = PostIndex::from_posts(vec![post("a", "标题", &[], "<p>很长的正文内容</p>", 1)]).unwrap();
+        let search = SearchIndex::build(&index);
+        assert!(!search.to_json().unwrap().contains("正文内容"));
+        assert_eq!(search.docs[0].text("<p>很长的正文内容</p>").trim(), "很长的正文内容");
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let index = sample_index();
+        assert_eq!(SearchIndex::from_json(&index.to_json().unwrap()).unwrap(), index);
+    }
+
+    #[test]
+    fn test_highlight_and_snippet() {
+        let parts = highlight("Dioxus 博客", "dioxus");
+        assert_eq!(
+            parts,
+            vec![
+                TextPart { text: "Dioxus".to_string(), highlight: true },
+                TextPart { text: " 博客".to_string(), highlight: false },
+            ]
+        );
+
+        // 非 ASCII 字母与 tokenize 一样不区分大小写
+        let parts = highlight("Über Ärger", "über ä");
+        assert_eq!(
+            parts,
+            vec![
+                TextPart { text: "Über".to_string(), highlight: true },
+                TextPart { text: " ".to_string(), highlight: false },
+                TextPart { text: "Ä".to_string(), highlight: true },
+                TextPart { text: "rger".to_string(), highlight: false },
+            ]
+        );
+        // 转小写后字符数变化时，片段仍与原文一一对应
+        let parts = highlight("İstanbul", "stanbul");
+        assert_eq!(parts.iter().map(|p| p.text.as_str()).collect::<String>(), "İstanbul");
+        assert_eq!(parts.last().unwrap(), &TextPart { text: "stanbul".to_string(), highlight: true });
+
+        let text = format!("{}个人博客{}", "前".repeat(50), "后".repeat(50));
+        let parts = snippet(&text, "个人博客", 20);
+        assert_eq!(parts.first().unwrap().text, "…");
+        assert_eq!(parts.last().unwrap().text, "…");
+        assert!(parts.iter().any(|p| p.highlight && p.text == "个人博客"));
+    }
+}
@@ -0,0 +1,244 @@
+use crate::feed::escape_xml;
+use crate::site::post_path;
+use crate::{Post, SiteConfig};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// 页面 `<head>` 中的一条 `<meta>` 标签
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetaTag {
+    /// 属性名：`name`（普通与 Twitter 标签）或 `property`（Open Graph 标签）
+    pub attr: String,
+    /// 属性值，如 `description`、`og:title`
+    pub key: String,
+    /// 标签内容
+    pub content: String,
+}
+
+/// 单个页面的 SEO 元数据
+///
+/// SPA 通过 `document::` 元素写入 `<head>`，静态预渲染时用 `to_head_html` 直接输出，
+/// 两处使用同一份数据
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageMeta {
+    /// 完整的页面标题（含站点名）
+    pub title: String,
+    /// 页面描述
+    pub description: String,
+    /// 规范链接（绝对 URL）
+    pub canonical: String,
+    /// 分享卡片图片（绝对 URL）
+    pub image: Option<String>,
+    /// Open Graph 类型：`website` 或 `article`
+    pub og_type: String,
+    /// `BlogPosting` 结构化数据（JSON-LD），仅文章页有
+    pub json_ld: Option<String>,
+}
+
+/// 将图片地址转换为绝对 URL，已是完整 URL 的保持不变
+fn absolute_image_url(site: &SiteConfig, image: &str) -> String {
+    if image.starts_with("http://") || image.starts_with("https://") {
+        image.to_string()
+    } else {
+        site.absolute_url(image)
+    }
+}
+
+impl PageMeta {
+    /// 普通页面；`title` 为 `None` 时表示首页，只使用站点标题
+    pub fn page(site: &SiteConfig, title: Option<&str>, path: &str) -> Self {
+        let title = match title {
+            Some(title) => format!("{} - {}", title, site.title),
+            None => site.title.clone(),
+        };
+        Self {
+            title,
+            description: site.description.clone(),
+            canonical: site.absolute_url(path),
+            image: None,
+            og_type: "website".to_string(),
+            json_ld: None,
+        }
+    }
+
+    /// 文章页，描述取自摘录（`summary` 优先），分享图片取自 `cover_image`
+    pub fn post(site: &SiteConfig, post: &Post) -> Self {
+        let metadata = &post.metadata;
+        let canonical = site.absolute_url(&post_path(&post.slug()));
+        let description = Some(post.excerpt_text())
+            .filter(|excerpt| !excerpt.is_empty())
+            .unwrap_or_else(|| site.description.clone());
+        let image = metadata
+            .cover_image
+            .as_deref()
+            .map(|image| absolute_image_url(site, image));
+        let author = metadata.author.clone().unwrap_or_else(|| site.author.clone());
+
+        let mut ld = json!({
+            "@context": "https://schema.org",
+            "@type": "BlogPosting",
+            "headline": metadata.title,
+            "description": description,
+            "url": canonical,
+            "mainEntityOfPage": { "@type": "WebPage", "@id": canonical },
+            "author": { "@type": "Person", "name": author },
+            "inLanguage": site.language,
+        });
+        if let Some(date) = metadata.date {
+            ld["datePublished"] = json!(date.format("%Y-%m-%d").to_string());
+        }
+        if let Some(updated) = metadata.updated {
+            ld["dateModified"] = json!(updated.format("%Y-%m-%dT%H:%M:%S").to_string());
+        }
+        if let Some(image) = &image {
+            ld["image"] = json!(image);
+        }
+        if let Some(tags) = metadata.tags.as_ref().filter(|tags| !tags.is_empty()) {
+            ld["keywords"] = json!(tags.join(", "));
+        }
+
+        Self {
+            title: format!("{} - {}", metadata.title, site.title),
+            description,
+            canonical,
+            image,
+            og_type: "article".to_string(),
+            // 防止内容中的 `</script>` 提前结束脚本标签
+            json_ld: Some(ld.to_string().replace("</", "<\\/")),
+        }
+    }
+
+    /// 描述、Open Graph 与 Twitter 卡片标签
+    pub fn meta_tags(&self) -> Vec<MetaTag> {
+        let tag = |attr: &str, key: &str, content: &str| MetaTag {
+            attr: attr.to_string(),
+            key: key.to_string(),
+            content: content.to_string(),
+        };
+        let card = if self.image.is_some() {
+            "summary_large_image"
+        } else {
+            "summary"
+        };
+
+        let mut tags = vec![
+            tag("name", "description", &self.description),
+            tag("property", "og:title", &self.title),
+            tag("property", "og:description", &self.description),
+            tag("property", "og:type", &self.og_type),
+            tag("property", "og:url", &self.canonical),
+            tag("name", "twitter:card", card),
+            tag("name", "twitter:title", &self.title),
+            tag("name", "twitter:description", &self.description),
+        ];
+        if let Some(image) = &self.image {
+            tags.push(tag("property", "og:image", image));
+            tags.push(tag("name", "twitter:image", image));
+        }
+        tags
+    }
+
+    /// 输出到预渲染页面 `<head>` 中的 HTML
+    pub fn to_head_html(&self) -> String {
+        let mut html = format!("<title>{}</title>\n", escape_xml(&self.title));
+        for tag in self.meta_tags() {
+            html.push_str(&format!(
+                "<meta {}=\"{}\" content=\"{}\">\n",
+                tag.attr,
+                escape_xml(&tag.key),
+                escape_xml(&tag.content)
+            ));
+        }
+        html.push_str(&format!(
+            "<link rel=\"canonical\" href=\"{}\">\n",
+            escape_xml(&self.canonical)
+        ));
+        if let Some(json_ld) = &self.json_ld {
+            html.push_str(&format!(
+                "<script type=\"application/ld+json\">{}</script>\n",
+                json_ld
+            ));
+        }
+        html
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PostMetadata;
+    use chrono::NaiveDate;
+
+    fn site() -> SiteConfig {
+        SiteConfig {
+            title: "我的博客".to_string(),
+            description: "站点简介".to_string(),
+            base_url: "https://example.com".to_string(),
+            author: "Sisyphus".to_string(),
+            ..SiteConfig::default()
+        }
+    }
+
+    fn post() -> Post {
+        Post {
+            metadata: PostMetadata {
+                title: "Hello </script>".to_string(),
+                date: NaiveDate::from_ymd_opt(2025, 11, 23),
+                summary: Some("文章摘要".to_string()),
+                cover_image: Some("/assets/cover.png".to_string()),
+                tags: Some(vec!["Rust".to_string()]),
+                ..PostMetadata::default()
+            },
+            file_path: "2025-11-23-hello.md".to_string(),
+            content: String::new(),
+            html_content: String::new(),
+            ..Post::default()
+        }
+    }
+
+    #[test]
+    fn test_page_meta() {
+        let home = PageMeta::page(&site(), None, "/");
+        assert_eq!(home.title, "我的博客");
+        assert_eq!(home.canonical, "https://example.com/");
+
+        let tags = PageMeta::page(&site(), Some("标签"), "/blog/tags");
+        assert_eq!(tags.title, "标签 - 我的博客");
+        assert_eq!(tags.description, "站点简介");
+        assert!(tags.json_ld.is_none());
+        assert!(tags
+            .meta_tags()
+            .contains(&MetaTag {
+                attr: "name".to_string(),
+                key: "twitter:card".to_string(),
+                content: "summary".to_string(),
+            }));
+    }
+
+    #[test]
+    fn test_post_meta() {
+        let meta = PageMeta::post(&site(), &post());
+        assert_eq!(meta.description, "文章摘要");
+        assert_eq!(meta.canonical, "https://example.com/blog/hello");
+        assert_eq!(meta.image.as_deref(), Some("https://example.com/assets/cover.png"));
+        assert_eq!(meta.og_type, "article");
+
+        let json_ld = meta.json_ld.as_deref().unwrap();
+        assert!(!json_ld.contains("</script>"));
+        let ld: serde_json::Value = serde_json::from_str(json_ld).unwrap();
+        assert_eq!(ld["@type"], "BlogPosting");
+        assert_eq!(ld["headline"], "Hello </script>");
+        assert_eq!(ld["datePublished"], "2025-11-23");
+        assert_eq!(ld["author"]["name"], "Sisyphus");
+        assert_eq!(ld["keywords"], "Rust");
+    }
+
+    #[test]
+    fn test_head_html_is_escaped() {
+        let html = PageMeta::post(&site(), &post()).to_head_html();
+        assert!(html.contains("<title>Hello &lt;/script&gt; - 我的博客</title>"));
+        assert!(html.contains(r#"<meta property="og:image" content="https://example.com/assets/cover.png">"#));
+        assert!(html.contains(r#"<link rel="canonical" href="https://example.com/blog/hello">"#));
+        assert_eq!(html.matches("</script>").count(), 1);
+    }
+}
@@ -0,0 +1,296 @@
+use serde::{Deserialize, Serialize};
+
+/// 站点配置，对应仓库根目录的 `site.yaml`
+///
+/// build.rs 读取该文件并写入 `OUT_DIR/site.json`，运行时再反序列化，
+/// 保证构建产物与页面使用同一份配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SiteConfig {
+    /// 站点标题
+    #[serde(default = "default_title")]
+    pub title: String,
+    /// 站点简介
+    #[serde(default)]
+    pub description: String,
+    /// 站点根地址（不含末尾的 `/`），用于生成订阅源和绝对链接
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+    /// 默认作者，文章未指定作者时使用
+    #[serde(default)]
+    pub author: String,
+    /// 站点语言
+    #[serde(default = "default_language")]
+    pub language: String,
+    /// 列表页每页文章数
+    #[serde(default = "default_posts_per_page")]
+    pub posts_per_page: usize,
+    /// 订阅源中包含的最新文章数
+    #[serde(default = "default_feed_items")]
+    pub feed_items: usize,
+    /// robots.txt 中禁止抓取的路径
+    #[serde(default)]
+    pub robots_disallow: Vec<String>,
+    /// 允许使用的标签，为空时不限制（`blog lint` 检查）
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 允许使用的分类，为空时不限制（`blog lint` 检查）
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// 为 true 时文章中有失效的站内链接或锚点会使构建失败，否则只输出警告
+    #[serde(default)]
+    pub strict_links: bool,
+}
+
+fn default_title() -> String {
+    "我的博客".to_string()
+}
+
+fn default_base_url() -> String {
+    "http://localhost:8080".to_string()
+}
+
+fn default_language() -> String {
+    "zh-CN".to_string()
+}
+
+fn default_posts_per_page() -> usize {
+    10
+}
+
+fn default_feed_items() -> usize {
+    20
+}
+
+impl SiteConfig {
+    /// 从 YAML 字符串解析站点配置
+    #[cfg(feature = "markdown")]
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+
+    /// 从 `site.json` 内容解析站点配置
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// 序列化为 `site.json` 内容
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// 将站内路径（以 `/` 开头）转换为绝对 URL
+    pub fn absolute_url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), path.trim_start_matches('/'))
+    }
+}
+
+// 以下路径与应用中 `Route` 的定义保持一致，供构建时生成订阅源和站点地图使用
+
+/// 被固定路由 `/blog/tags`、`/blog/categories` 占用、不能作为文章 slug 的路径段
+pub const RESERVED_SLUGS: &[&str] = &["tags", "categories"];
+
+/// 文章页路径：`/blog/:slug`
+pub fn post_path(slug: &str) -> String {
+    format!("/blog/{}", encode_path_segment(slug))
+}
+
+/// 文章社交卡片图片路径：`/assets/og/:slug.png`（由 build.rs 生成到 `public/` 下）
+pub fn og_card_path(slug: &str) -> String {
+    format!("/assets/og/{}.png", encode_path_segment(slug))
+}
+
+/// 页面包资源路径：`/assets/posts/:slug/:file`（由 build.rs 复制到 `public/` 下）
+pub fn post_asset_path(slug: &str, file: &str) -> String {
+    let file: Vec<String> = file.split('/').map(encode_path_segment).collect();
+    format!("/assets/posts/{}/{}", encode_path_segment(slug), file.join("/"))
+}
+
+/// 博客列表第 `page` 页路径，第一页为 `/blog`
+pub fn blog_path(page: usize) -> String {
+    if page <= 1 {
+        "/blog".to_string()
+    } else {
+        format!("/blog/page/{}", page)
+    }
+}
+
+/// 标签列表第 `page` 页路径，第一页为 `/blog/tag/:tag`
+pub fn tag_path(tag: &str, page: usize) -> String {
+    let base = format!("/blog/tag/{}", encode_path_segment(tag));
+    if page <= 1 {
+        base
+    } else {
+        format!("{}/page/{}", base, page)
+    }
+}
+
+/// 分类列表第 `page` 页路径，第一页为 `/blog/category/:category`
+pub fn category_path(category: &str, page: usize) -> String {
+    let base = format!("/blog/category/{}", encode_path_segment(category));
+    if page <= 1 {
+        base
+    } else {
+        format!("{}/page/{}", base, page)
+    }
+}
+
+/// 标签或分类名能否安全地用作一段路径：解码后会成为输出目录中的一级目录名，
+/// 不能为空、`.`、`..`，也不能包含 `/` 或 `\`
+pub fn is_safe_path_segment(name: &str) -> bool {
+    !name.trim().is_empty() && name != "." && name != ".." && !name.contains(['/', '\\'])
+}
+
+/// 按浏览器的规则把相对路径解析为以 `/` 开头的站内路径
+pub(crate) fn resolve(base: &str, path: &str) -> String {
+    if path.starts_with('/') {
+        return path.to_string();
+    }
+    let mut segments: Vec<&str> = base.split('/').filter(|s| !s.is_empty()).collect();
+    // 相对路径相对于当前页面所在的“目录”
+    if !base.ends_with('/') {
+        segments.pop();
+    }
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    format!("/{}", segments.join("/"))
+}
+
+/// 链接是否带协议（`https:`、`mailto:`、`data:` 等）或为协议相对地址
+pub(crate) fn is_external(url: &str) -> bool {
+    url.starts_with("//")
+        || url.split_once(':').is_some_and(|(scheme, _)| {
+            !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        })
+}
+
+/// 对 URL 路径中的一段做百分号编码（保留 RFC 3986 的非保留字符）
+pub fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// `encode_path_segment` 的逆操作，无法解码为 UTF-8 时原样返回
+pub fn decode_path_segment(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = segment
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| segment.to_string())
+}
+
+impl Default for SiteConfig {
+    fn default() -> Self {
+        Self {
+            title: default_title(),
+            description: String::new(),
+            base_url: default_base_url(),
+            author: String::new(),
+            language: default_language(),
+            posts_per_page: default_posts_per_page(),
+            feed_items: default_feed_items(),
+            robots_disallow: Vec::new(),
+            tags: Vec::new(),
+            categories: Vec::new(),
+            strict_links: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_site_config_defaults() {
+        let config = SiteConfig::from_yaml("{}").unwrap();
+        assert_eq!(config, SiteConfig::default());
+
+        let config = SiteConfig::from_yaml("posts_per_page: 5").unwrap();
+        assert_eq!(config.posts_per_page, 5);
+    }
+
+    #[test]
+    fn test_absolute_url() {
+        let config = SiteConfig {
+            base_url: "https://example.com/".to_string(),
+            ..SiteConfig::default()
+        };
+        assert_eq!(config.absolute_url("/blog/hello"), "https://example.com/blog/hello");
+        assert_eq!(config.absolute_url("feed.xml"), "https://example.com/feed.xml");
+    }
+
+    #[test]
+    fn test_encode_path_segment() {
+        assert_eq!(encode_path_segment("Rust"), "Rust");
+        assert_eq!(encode_path_segment("博客"), "%E5%8D%9A%E5%AE%A2");
+        assert_eq!(encode_path_segment("a b/c"), "a%20b%2Fc");
+    }
+
+    #[test]
+    fn test_decode_path_segment() {
+        for segment in ["rust", "博客 开发", "C++", "100%"] {
+            assert_eq!(decode_path_segment(&encode_path_segment(segment)), segment);
+        }
+        // 非法的转义序列原样保留
+        assert_eq!(decode_path_segment("50%"), "50%");
+        assert_eq!(decode_path_segment("%zz"), "%zz");
+    }
+
+    #[test]
+    fn test_is_safe_path_segment() {
+        for name in ["Rust", "C++", "博客", "a..b"] {
+            assert!(is_safe_path_segment(name), "{}", name);
+        }
+        for name in ["", " ", ".", "..", "a/b", "/x", "a\\b"] {
+            assert!(!is_safe_path_segment(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_route_paths() {
+        assert_eq!(post_path("hello"), "/blog/hello");
+        assert_eq!(blog_path(1), "/blog");
+        assert_eq!(blog_path(3), "/blog/page/3");
+        assert_eq!(tag_path("博客", 1), "/blog/tag/%E5%8D%9A%E5%AE%A2");
+        assert_eq!(category_path("Test", 2), "/blog/category/Test/page/2");
+        assert_eq!(og_card_path("hello"), "/assets/og/hello.png");
+    }
+
+    #[test]
+    fn test_site_config_json_roundtrip() {
+        let config = SiteConfig::default();
+        let json = config.to_json().unwrap();
+        assert_eq!(SiteConfig::from_json(&json).unwrap(), config);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::feed::escape_xml;
+use crate::site::{blog_path, category_path, post_path, tag_path};
+use crate::{Post, PostIndex, SiteConfig};
+use chrono::NaiveDate;
+
+/// 站点地图中的一条 URL
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapEntry {
+    /// 站内路径
+    pub path: String,
+    /// 最后修改日期
+    pub lastmod: Option<NaiveDate>,
+}
+
+/// 文章的最后修改日期：优先 `updated`，否则为 `date`
+fn post_lastmod(post: &Post) -> Option<NaiveDate> {
+    post.metadata
+        .updated
+        .map(|dt| dt.date())
+        .or(post.metadata.date)
+}
+
+/// 一组文章中最新的修改日期
+fn latest_lastmod<'a>(posts: impl IntoIterator<Item = &'a Post>) -> Option<NaiveDate> {
+    posts.into_iter().filter_map(post_lastmod).max()
+}
+
+/// 列出站点中所有可访问的页面，顺序与 `Route` 的定义一致：
+/// 首页、博客列表各页、标签/分类总览、每篇文章、每个标签和分类的各页
+pub fn sitemap_entries(index: &PostIndex, site: &SiteConfig) -> Vec<SitemapEntry> {
+    let per_page = site.posts_per_page;
+    let site_lastmod = latest_lastmod(&index.posts);
+    let mut entries = vec![SitemapEntry {
+        path: "/".to_string(),
+        lastmod: site_lastmod,
+    }];
+
+    // 博客列表各页
+    let total_pages = index.get_page(1, per_page).total_pages;
+    for page in 1..=total_pages {
+        let listing = index.get_page(page, per_page);
+        entries.push(SitemapEntry {
+            path: blog_path(page),
+            lastmod: latest_lastmod(listing.posts.iter().copied()),
+        });
+    }
+
+    entries.push(SitemapEntry {
+        path: "/blog/tags".to_string(),
+        lastmod: site_lastmod,
+    });
+    entries.push(SitemapEntry {
+        path: "/blog/categories".to_string(),
+        lastmod: site_lastmod,
+    });
+
+    // 每篇文章（按日期从新到旧）
+    for post in index.get_recent_posts(index.posts.len()) {
+        entries.push(SitemapEntry {
+            path: post_path(&post.slug()),
+            lastmod: post_lastmod(post),
+        });
+    }
+
+    // 每个标签、分类的各页（按名称排序，保证输出稳定）
+    let mut tags: Vec<&String> = index.tags.keys().collect();
+    tags.sort();
+    for tag in tags {
+        let total_pages = index.get_tag_page(tag, 1, per_page).total_pages;
+        for page in 1..=total_pages {
+            let listing = index.get_tag_page(tag, page, per_page);
+            entries.push(SitemapEntry {
+                path: tag_path(tag, page),
+                lastmod: latest_lastmod(listing.posts.iter().copied()),
+            });
+        }
+    }
+
+    let mut categories: Vec<&String> = index.categories.keys().collect();
+    categories.sort();
+    for category in categories {
+        let total_pages = index.get_category_page(category, 1, per_page).total_pages;
+        for page in 1..=total_pages {
+            let listing = index.get_category_page(category, page, per_page);
+            entries.push(SitemapEntry {
+                path: category_path(category, page),
+                lastmod: latest_lastmod(listing.posts.iter().copied()),
+            });
+        }
+    }
+
+    entries
+}
+
+/// 生成 `sitemap.xml`
+pub fn generate_sitemap(index: &PostIndex, site: &SiteConfig) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for entry in sitemap_entries(index, site) {
+        xml.push_str("  <url>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", escape_xml(&site.absolute_url(&entry.path))));
+        if let Some(lastmod) = entry.lastmod {
+            xml.push_str(&format!("    <lastmod>{}</lastmod>\n", lastmod.format("%Y-%m-%d")));
+        }
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// 生成 `robots.txt`，禁止抓取的路径来自 `site.yaml` 的 `robots_disallow`
+pub fn generate_robots(site: &SiteConfig) -> String {
+    let mut robots = String::from("User-agent: *\n");
+    if site.robots_disallow.is_empty() {
+        robots.push_str("Allow: /\n");
+    }
+    for path in &site.robots_disallow {
+        robots.push_str(&format!("Disallow: {}\n", path));
+    }
+    robots.push_str(&format!("\nSitemap: {}\n", site.absolute_url("/sitemap.xml")));
+    robots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn site() -> SiteConfig {
+        SiteConfig {
+            base_url: "https://example.com".to_string(),
+            robots_disallow: vec!["/404".to_string()],
+            ..SiteConfig::default()
+        }
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_sitemap_for_sample_blogs() {
+        let blogs = std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../../blogs"));
+        let index = crate::scan_blogs_directory(blogs).unwrap();
+        let entries = sitemap_entries(&index, &site());
+        let find = |path: &str| entries.iter().find(|e| e.path == path).cloned();
+        let date = |s: &str| crate::parse_date(s);
+
+        assert_eq!(find("/").unwrap().lastmod, date("2025-11-24"));
+        assert_eq!(find("/blog").unwrap().lastmod, date("2025-11-24"));
+        assert!(find("/blog/tags").is_some());
+        assert!(find("/blog/categories").is_some());
+        assert_eq!(find("/blog/welcome-to-dioxus-blog").unwrap().lastmod, date("2025-11-23"));
+        assert_eq!(find("/blog/math-test").unwrap().lastmod, date("2025-11-24"));
+        assert_eq!(find("/blog/tag/%E5%8D%9A%E5%AE%A2").unwrap().lastmod, date("2025-11-23"));
+        assert_eq!(find("/blog/category/Test").unwrap().lastmod, date("2025-11-24"));
+        // 两篇文章只需要一页
+        assert!(find("/blog/page/2").is_none());
+
+        let xml = generate_sitemap(&index, &site());
+        assert!(xml.contains("<loc>https://example.com/blog/math-test</loc>"));
+        assert!(xml.contains("<lastmod>2025-11-24</lastmod>"));
+        assert_eq!(xml.matches("<url>").count(), entries.len());
+    }
+
+    #[test]
+    fn test_sitemap_paginates_listings() {
+        let posts = (1..=3).map(|day| {
+            let mut post = Post {
+                metadata: crate::PostMetadata::new(),
+                file_path: format!("post-{}.md", day),
+                content: String::new(),
+                html_content: String::new(),
+                ..Post::default()
+            };
+            post.metadata.date = crate::parse_date(&format!("2025-01-0{}", day));
+            post.metadata.tags = Some(vec!["Rust".to_string()]);
+            post
+        });
+        let index = PostIndex::from_posts(posts).unwrap();
+        let site = SiteConfig {
+            posts_per_page: 2,
+            ..site()
+        };
+        let paths: Vec<String> = sitemap_entries(&index, &site).into_iter().map(|e| e.path).collect();
+        assert!(paths.contains(&"/blog/page/2".to_string()));
+        assert!(paths.contains(&"/blog/tag/Rust/page/2".to_string()));
+        assert!(!paths.contains(&"/blog/page/3".to_string()));
+    }
+
+    #[test]
+    fn test_generate_robots() {
+        let robots = generate_robots(&site());
+        assert!(robots.contains("Disallow: /404\n"));
+        assert!(robots.contains("Sitemap: https://example.com/sitemap.xml"));
+
+        let open = generate_robots(&SiteConfig::default());
+        assert!(open.contains("Allow: /\n"));
+    }
+}
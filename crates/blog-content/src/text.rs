@@ -0,0 +1,178 @@
+/// 是否为中日韩文字（这些文字之间没有空格分词）
+pub fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'   // 平假名、片假名
+        | '\u{3400}'..='\u{4DBF}' // CJK 扩展 A
+        | '\u{4E00}'..='\u{9FFF}' // CJK 统一表意文字
+        | '\u{AC00}'..='\u{D7AF}' // 韩文音节
+        | '\u{F900}'..='\u{FAFF}' // CJK 兼容表意文字
+        | '\u{20000}'..='\u{2FA1F}' // CJK 扩展 B 及以后
+    )
+}
+
+/// 中日韩文字每分钟阅读字数
+const CJK_CHARS_PER_MINUTE: f64 = 400.0;
+/// 其他语言每分钟阅读单词数
+const WORDS_PER_MINUTE: f64 = 200.0;
+
+/// 字数统计结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WordCount {
+    /// 中日韩文字数（逐字计数）
+    pub cjk: usize,
+    /// 其他语言的单词数（按空白和标点分隔）
+    pub words: usize,
+}
+
+impl WordCount {
+    /// 总字数
+    pub fn total(&self) -> usize {
+        self.cjk + self.words
+    }
+
+    /// 预计阅读时间（分钟，向上取整）；有内容时至少为 1 分钟
+    pub fn reading_minutes(&self) -> usize {
+        if self.total() == 0 {
+            return 0;
+        }
+        let minutes = self.cjk as f64 / CJK_CHARS_PER_MINUTE + self.words as f64 / WORDS_PER_MINUTE;
+        (minutes.ceil() as usize).max(1)
+    }
+}
+
+impl std::ops::AddAssign for WordCount {
+    fn add_assign(&mut self, other: Self) {
+        self.cjk += other.cjk;
+        self.words += other.words;
+    }
+}
+
+/// 统计纯文本的字数：中日韩文字逐字计数，其他字母数字按连续片段计为一个单词
+pub fn count_words(text: &str) -> WordCount {
+    let mut count = WordCount::default();
+    let mut in_word = false;
+    for c in text.chars() {
+        if is_cjk(c) {
+            count.cjk += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                count.words += 1;
+                in_word = true;
+            }
+        } else if c != '\'' && c != '’' {
+            // 撇号不拆分单词（如 don't）
+            in_word = false;
+        }
+    }
+    count
+}
+
+/// 截取前 `max_chars` 个字符，被截断时以省略号结尾
+pub fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", text[..end].trim_end()),
+        None => text.to_string(),
+    }
+}
+
+/// 解码常见的 HTML 实体
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = entity.strip_prefix('#')?;
+            let value = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(value)
+        }
+    }
+}
+
+/// 把渲染后的 HTML 转换为纯文本：去掉标签、解码实体并合并空白
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '<' => {
+                // 标签视为空白，避免相邻段落的文字粘在一起
+                rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+                text.push(' ');
+            }
+            '&' => {
+                let decoded = rest
+                    .find(';')
+                    .filter(|end| *end <= 10)
+                    .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+                match decoded {
+                    Some((c, end)) => {
+                        text.push(c);
+                        rest = &rest[end + 1..];
+                    }
+                    None => {
+                        text.push('&');
+                        rest = &rest[1..];
+                    }
+                }
+            }
+            _ => {
+                text.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_cjk() {
+        assert!(is_cjk('博'));
+        assert!(is_cjk('の'));
+        assert!(!is_cjk('a'));
+        assert!(!is_cjk('，'));
+    }
+
+    #[test]
+    fn test_count_words() {
+        assert_eq!(count_words("你好，世界"), WordCount { cjk: 4, words: 0 });
+        assert_eq!(count_words("Hello, world! don't stop"), WordCount { cjk: 0, words: 4 });
+        assert_eq!(count_words("使用 Rust 2021 构建"), WordCount { cjk: 4, words: 2 });
+        assert_eq!(count_words("Dioxus博客"), WordCount { cjk: 2, words: 1 });
+    }
+
+    #[test]
+    fn test_reading_minutes() {
+        assert_eq!(WordCount::default().reading_minutes(), 0);
+        assert_eq!(WordCount { cjk: 10, words: 0 }.reading_minutes(), 1);
+        assert_eq!(WordCount { cjk: 800, words: 0 }.reading_minutes(), 2);
+        assert_eq!(WordCount { cjk: 400, words: 201 }.reading_minutes(), 3);
+    }
+
+    #[test]
+    fn test_truncate_chars() {
+        assert_eq!(truncate_chars("短文本", 10), "短文本");
+        assert_eq!(truncate_chars("一二三四五", 3), "一二三…");
+        assert_eq!(truncate_chars("ab cd", 3), "ab…");
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        assert_eq!(
+            html_to_text("<h1>标题</h1>\n<p>A &amp; B &lt;tag&gt; &#39;x&#x27;</p><p>下一段</p>"),
+            "标题 A & B <tag> 'x' 下一段"
+        );
+        assert_eq!(html_to_text("AT&T"), "AT&T");
+    }
+}
@@ -1,6 +1,11 @@
 pub mod lazy_image;
 pub mod responsive_image;
+pub mod post_card;
+pub mod pagination;
+pub mod table_of_contents;
 
 pub use lazy_image::LazyImage;
 pub use responsive_image::ResponsiveImage;
-
+pub use post_card::{PostCard, TagChip};
+pub use pagination::Pagination;
+pub use table_of_contents::TableOfContents;
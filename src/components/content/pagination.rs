@@ -0,0 +1,61 @@
+use crate::routes::Route;
+use dioxus::prelude::*;
+
+/// 当前页两侧显示的页码数量
+const PAGE_WINDOW: usize = 2;
+
+/// 分页导航：上一页、页码和下一页
+///
+/// `pages[i]` 是第 `i + 1` 页的路由；只有一页时不渲染
+#[component]
+pub fn Pagination(current: usize, pages: Vec<Route>) -> Element {
+    let total = pages.len();
+    if total <= 1 {
+        return rsx! {};
+    }
+
+    let link_class = "px-3 py-1 rounded border border-gray-200 dark:border-gray-700 text-gray-700 dark:text-gray-300 hover:bg-gray-100 dark:hover:bg-gray-700 transition-colors";
+    let current_class = "px-3 py-1 rounded bg-blue-600 text-white";
+
+    rsx! {
+        nav {
+            class: "mt-8 flex flex-wrap items-center justify-center gap-2",
+            aria_label: "分页",
+            if current > 1 {
+                Link { to: pages[current - 2].clone(), class: link_class, "← 上一页" }
+            }
+            for item in page_items(current, total) {
+                {
+                    match item {
+                        Some(page) if page == current => rsx! {
+                            span { class: current_class, aria_current: "page", "{page}" }
+                        },
+                        Some(page) => rsx! {
+                            Link { to: pages[page - 1].clone(), class: link_class, "{page}" }
+                        },
+                        None => rsx! {
+                            span { class: "px-2 text-gray-500", "…" }
+                        },
+                    }
+                }
+            }
+            if current < total {
+                Link { to: pages[current].clone(), class: link_class, "下一页 →" }
+            }
+        }
+    }
+}
+
+/// 需要显示的页码：首页、末页和当前页附近的页码，`None` 表示省略号
+fn page_items(current: usize, total: usize) -> Vec<Option<usize>> {
+    let mut items = Vec::new();
+    for page in 1..=total {
+        let near_current = page + PAGE_WINDOW >= current && page <= current + PAGE_WINDOW;
+        if page == 1 || page == total || near_current {
+            items.push(Some(page));
+        } else if items.last() != Some(&None) {
+            items.push(None);
+        }
+    }
+    items
+}
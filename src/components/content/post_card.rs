@@ -0,0 +1,55 @@
+use crate::content::Post;
+use crate::routes::Route;
+use dioxus::prelude::*;
+
+/// 文章列表卡片：标题、日期、摘要和可点击的标签
+#[component]
+pub fn PostCard(post: Post) -> Element {
+    rsx! {
+        div { class: "bg-white dark:bg-gray-800 rounded-lg shadow-md hover:shadow-lg transition-shadow p-6",
+            div { class: "flex flex-col md:flex-row gap-4",
+                // 文章信息
+                div { class: "flex-1",
+                    h2 { class: "text-2xl font-semibold text-gray-900 dark:text-white mb-2",
+                        Link {
+                            to: Route::BlogPost { slug: post.slug() },
+                            class: "hover:text-blue-600 dark:hover:text-blue-400 transition-colors",
+                            "{post.metadata.title}"
+                        }
+                    }
+                    div {
+                        class: "flex items-center gap-4 mb-2 text-sm text-gray-500 dark:text-gray-500",
+                        if let Some(date) = post.metadata.date {
+                            span { "{date}" }
+                        }
+                        if post.word_count > 0 {
+                            span { "{post.word_count} 字 · 约 {post.reading_time} 分钟" }
+                        }
+                    }
+                    div { class: "text-gray-600 dark:text-gray-400 mb-4",
+                        dangerous_inner_html: "{post.excerpt()}",
+                    }
+                    div { class: "flex flex-wrap gap-2",
+                        if let Some(tags) = &post.metadata.tags {
+                            for tag in tags {
+                                TagChip { tag: tag.clone() }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 标签小标签，链接到对应的标签页
+#[component]
+pub fn TagChip(tag: String) -> Element {
+    rsx! {
+        Link {
+            to: Route::BlogTag { tag: tag.clone() },
+            class: "px-2 py-1 bg-gray-100 dark:bg-gray-700 text-gray-700 dark:text-gray-300 hover:bg-blue-100 dark:hover:bg-blue-900 rounded text-sm transition-colors",
+            "{tag}"
+        }
+    }
+}
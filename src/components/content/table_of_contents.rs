@@ -0,0 +1,93 @@
+use crate::content::TocEntry;
+use dioxus::prelude::*;
+
+/// 目录中显示的最浅和最深标题级别（文章标题已在页头显示，正文中的一级标题不列出）
+const MIN_LEVEL: u8 = 2;
+const MAX_LEVEL: u8 = 4;
+
+/// 滚动到距离视口顶部多少像素以内时，认为进入了该章节
+#[cfg(target_arch = "wasm32")]
+const SPY_OFFSET: f64 = 96.0;
+
+/// 文章目录侧栏：固定在视口中，滚动时高亮当前所在章节
+#[component]
+pub fn TableOfContents(toc: Vec<TocEntry>) -> Element {
+    let entries: Vec<TocEntry> = toc
+        .into_iter()
+        .filter(|entry| (MIN_LEVEL..=MAX_LEVEL).contains(&entry.level))
+        .collect();
+    let base_level = entries.iter().map(|entry| entry.level).min().unwrap_or(MIN_LEVEL);
+
+    #[allow(unused_mut)]
+    let mut active = use_signal(|| None::<String>);
+    #[cfg(target_arch = "wasm32")]
+    use_scroll_spy(entries.iter().map(|entry| entry.id.clone()).collect(), active);
+
+    if entries.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        nav {
+            class: "sticky top-24 max-h-[calc(100vh-8rem)] overflow-y-auto text-sm",
+            "aria-label": "目录",
+            p { class: "font-semibold text-gray-900 dark:text-white mb-3", "目录" }
+            ul {
+                class: "space-y-2 border-l border-gray-200 dark:border-gray-700",
+                for entry in entries.iter() {
+                    li {
+                        key: "{entry.id}",
+                        style: "padding-left: {(entry.level - base_level) as f32 * 0.75 + 0.75}rem",
+                        a {
+                            href: "#{entry.id}",
+                            class: if active.read().as_deref() == Some(entry.id.as_str()) {
+                                "block -ml-px border-l-2 border-blue-600 pl-2 text-blue-600 dark:text-blue-400 font-medium"
+                            } else {
+                                "block -ml-px border-l-2 border-transparent pl-2 text-gray-600 dark:text-gray-400 hover:text-blue-600 dark:hover:text-blue-400"
+                            },
+                            "{entry.title}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 监听页面滚动，把最后一个越过视口顶部的标题记为当前章节
+#[cfg(target_arch = "wasm32")]
+fn use_scroll_spy(ids: Vec<String>, mut active: Signal<Option<String>>) {
+    use std::rc::Rc;
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let listener = use_hook(move || {
+        let window = web_sys::window()?;
+        let document = window.document()?;
+        let update = move || {
+            let current = ids
+                .iter()
+                .filter(|id| {
+                    document
+                        .get_element_by_id(id)
+                        .is_some_and(|heading| heading.get_bounding_client_rect().top() <= SPY_OFFSET)
+                })
+                .next_back()
+                .cloned();
+            if *active.peek() != current {
+                active.set(current);
+            }
+        };
+        let closure = Closure::<dyn FnMut()>::new(update);
+        window
+            .add_event_listener_with_callback("scroll", closure.as_ref().unchecked_ref())
+            .ok()?;
+        Some(Rc::new(closure))
+    });
+
+    use_drop(move || {
+        if let (Some(window), Some(closure)) = (web_sys::window(), listener.as_ref()) {
+            let _ = window
+                .remove_event_listener_with_callback("scroll", (**closure).as_ref().unchecked_ref());
+        }
+    });
+}
@@ -1,11 +1,20 @@
 use dioxus::prelude::*;
 use crate::routes::Route;
-use crate::components::layout::ThemeToggle;
+use crate::components::layout::{SearchBox, SeoHead, ThemeToggle};
+use crate::utils::AppState;
 
 /// 主布局组件，包含导航栏和页脚
 #[component]
 pub fn MainLayout() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+    let route = use_route::<Route>();
+    let meta = {
+        let state = app_state.read();
+        route.page_meta(&state.post_index, &state.site)
+    };
+
     rsx! {
+        SeoHead { meta }
         div {
             class: "min-h-screen flex flex-col",
             // 导航栏
@@ -34,6 +43,8 @@ pub fn MainLayout() -> Element {
                                 class: "text-gray-700 dark:text-gray-300 hover:text-blue-600 dark:hover:text-blue-400 transition-colors",
                                 "博客"
                             }
+                            // 全文搜索
+                            SearchBox {}
                             // 暗色模式切换按钮
                             ThemeToggle {}
                         }
@@ -1,6 +1,10 @@
 pub mod main_layout;
+pub mod seo_head;
+pub mod search_box;
 pub mod theme_toggle;
 
 pub use main_layout::MainLayout;
+pub use seo_head::SeoHead;
+pub use search_box::SearchBox;
 pub use theme_toggle::ThemeToggle;
 
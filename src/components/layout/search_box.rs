@@ -0,0 +1,34 @@
+use dioxus::prelude::*;
+use crate::routes::Route;
+
+/// 导航栏中的搜索框，回车后跳转到搜索结果页
+///
+/// 表单同时带有 `action`，预渲染页面在脚本加载前也能直接提交到 `/search?q=`
+#[component]
+pub fn SearchBox() -> Element {
+    let mut query = use_signal(String::new);
+
+    rsx! {
+        form {
+            action: "/search",
+            method: "get",
+            role: "search",
+            onsubmit: move |event| {
+                event.prevent_default();
+                let q = query.read().trim().to_string();
+                if !q.is_empty() {
+                    navigator().push(Route::Search { q });
+                }
+            },
+            input {
+                r#type: "search",
+                name: "q",
+                placeholder: "搜索文章...",
+                "aria-label": "搜索文章",
+                class: "w-40 md:w-56 px-3 py-1 rounded-md border border-gray-300 dark:border-gray-600 bg-white dark:bg-gray-700 text-gray-900 dark:text-white text-sm focus:outline-none focus:ring-2 focus:ring-blue-500",
+                value: "{query}",
+                oninput: move |event| query.set(event.value()),
+            }
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use dioxus::prelude::*;
+use crate::content::PageMeta;
+
+/// 把页面的 SEO 元数据写入 `<head>`：标题、描述、规范链接、Open Graph/Twitter 标签和 JSON-LD
+#[component]
+pub fn SeoHead(meta: PageMeta) -> Element {
+    rsx! {
+        document::Title { "{meta.title}" }
+        for tag in meta.meta_tags() {
+            if tag.attr == "property" {
+                document::Meta { key: "{tag.key}", property: tag.key.clone(), content: tag.content.clone() }
+            } else {
+                document::Meta { key: "{tag.key}", name: tag.key.clone(), content: tag.content.clone() }
+            }
+        }
+        document::Link { rel: "canonical", href: meta.canonical.clone() }
+        if let Some(json_ld) = meta.json_ld.clone() {
+            document::Script { r#type: "application/ld+json", {json_ld} }
+        }
+    }
+}
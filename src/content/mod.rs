@@ -1,13 +1,90 @@
-pub mod metadata;
-pub mod markdown;
-pub mod index;
+pub use blog_content::{tag_cloud_weight, PageMeta, Post, PostIndex, SearchIndex, SiteConfig, TocEntry};
 
-use include_dir::{include_dir, Dir};
+use std::sync::OnceLock;
 
-// 强制重新编译以包含最新的博客文章
-pub static BLOGS_DIR: Dir = include_dir!("blogs");
+/// build.rs 生成的文章索引（仅包含元数据，不含正文）
+static POSTS_INDEX_JSON: &str = include_str!("../../assets/posts_index.json");
 
-pub use metadata::PostMetadata;
-pub use markdown::{Post, parse_markdown_file, parse_markdown_content, render_markdown, process_markdown_file};
-pub use index::{PostIndex, scan_blogs_directory, load_embedded_blogs};
+/// build.rs 根据 `site.yaml` 生成的站点配置
+static SITE_JSON: &str = include_str!(concat!(env!("OUT_DIR"), "/site.json"));
 
+/// build.rs 生成的全文搜索索引
+static SEARCH_INDEX_JSON: &str = include_str!(concat!(env!("OUT_DIR"), "/search_index.json"));
+
+// build.rs 生成的预渲染正文：`POST_BODIES: &[(&str, &str)]`
+include!(concat!(env!("OUT_DIR"), "/post_bodies.rs"));
+
+/// 加载构建时生成的文章索引 (WASM 兼容)
+///
+/// 列表页只需要元数据，因此这里不会解析或渲染任何 Markdown 正文
+pub fn load_post_index() -> PostIndex {
+    match PostIndex::from_json(POSTS_INDEX_JSON) {
+        Ok(index) => index,
+        Err(e) => {
+            #[cfg(target_arch = "wasm32")]
+            web_sys::console::error_1(&format!("加载文章索引失败: {:?}", e).into());
+            #[cfg(not(target_arch = "wasm32"))]
+            eprintln!("警告：加载文章索引失败: {:?}", e);
+            PostIndex::new()
+        }
+    }
+}
+
+/// 加载构建时生成的站点配置，失败时使用默认配置
+pub fn load_site_config() -> SiteConfig {
+    SiteConfig::from_json(SITE_JSON).unwrap_or_default()
+}
+
+/// 根据 slug 获取构建时预渲染的文章 HTML
+pub fn post_html(slug: &str) -> Option<&'static str> {
+    POST_BODIES
+        .iter()
+        .find(|(post_slug, _)| *post_slug == slug)
+        .map(|(_, html)| *html)
+}
+
+/// 全文搜索索引，第一次搜索时才解析
+pub fn search_index() -> &'static SearchIndex {
+    static INDEX: OnceLock<SearchIndex> = OnceLock::new();
+    INDEX.get_or_init(|| match SearchIndex::from_json(SEARCH_INDEX_JSON) {
+        Ok(index) => index,
+        Err(e) => {
+            #[cfg(target_arch = "wasm32")]
+            web_sys::console::error_1(&format!("加载搜索索引失败: {:?}", e).into());
+            #[cfg(not(target_arch = "wasm32"))]
+            eprintln!("警告：加载搜索索引失败: {:?}", e);
+            SearchIndex::default()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_index_parses() {
+        let index = load_post_index();
+        assert_eq!(index.posts.len(), index.sorted_by_date.len());
+    }
+
+    #[test]
+    fn test_embedded_site_config_parses() {
+        assert!(SiteConfig::from_json(SITE_JSON).is_ok());
+    }
+
+    #[test]
+    fn test_embedded_search_index_covers_every_post() {
+        let search = SearchIndex::from_json(SEARCH_INDEX_JSON).unwrap();
+        assert_eq!(search.docs.len(), load_post_index().posts.len());
+    }
+
+    #[test]
+    fn test_every_post_has_prerendered_html() {
+        let index = load_post_index();
+        for post in index.get_all_posts() {
+            let slug = post.metadata.slug.as_deref().unwrap_or_default();
+            assert!(post_html(slug).is_some(), "缺少预渲染正文: {}", slug);
+        }
+    }
+}
@@ -6,17 +6,42 @@ mod routes;
 mod content;
 mod components;
 mod utils;
+#[cfg(feature = "ssg")]
+mod ssg;
 
 static CSS: Asset = asset!("/assets/main.css");
 static TAILWIND_CSS: Asset = asset!("/assets/tailwind.css");
 static THEME_JS: Asset = asset!("/assets/js/theme.js");
-static KATEX_INIT_JS: Asset = asset!("/assets/js/katex-init.js");
-static HIGHLIGHT_INIT_JS: Asset = asset!("/assets/js/highlight-init.js");
 
+#[cfg(not(feature = "ssg"))]
 fn main() {
+    #[cfg(target_arch = "wasm32")]
+    clear_prerendered_content();
     dioxus::launch(App);
 }
 
+/// 清空挂载点中预渲染的静态内容
+///
+/// 预渲染页面不带 hydration 数据，客户端会在挂载点中重新渲染整个应用，
+/// 不先清空的话页面内容会出现两遍
+#[cfg(all(target_arch = "wasm32", not(feature = "ssg")))]
+fn clear_prerendered_content() {
+    if let Some(main) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id("main"))
+    {
+        main.set_inner_html("");
+    }
+}
+
+#[cfg(feature = "ssg")]
+fn main() {
+    if let Err(e) = ssg::run() {
+        eprintln!("静态站点生成失败: {:#}", e);
+        std::process::exit(1);
+    }
+}
+
 #[component]
 fn App() -> Element {
     // 创建全局状态
@@ -29,8 +54,10 @@ fn App() -> Element {
         document::Stylesheet { href: TAILWIND_CSS }
         document::Stylesheet { href: CSS }
         document::Script { src: THEME_JS }
-        document::Script { src: KATEX_INIT_JS }
-        document::Script { src: HIGHLIGHT_INIT_JS }
+        // 订阅源（由 build.rs 生成到 public/）
+        document::Link { rel: "alternate", r#type: "application/rss+xml", title: "RSS", href: "/feed.xml" }
+        document::Link { rel: "alternate", r#type: "application/atom+xml", title: "Atom", href: "/atom.xml" }
+        document::Link { rel: "alternate", r#type: "application/feed+json", title: "JSON Feed", href: "/feed.json" }
         Router::<Route> {}
     }
 }
@@ -0,0 +1,39 @@
+use crate::routes::Route;
+use crate::utils::AppState;
+use dioxus::prelude::*;
+
+/// 分类总览页组件：列出所有分类及文章数
+#[component]
+pub fn BlogCategories() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+    let state = app_state.read();
+    let category_counts = state.post_index.category_counts();
+
+    rsx! {
+        div {
+            class: "container mx-auto px-4 py-8",
+            h1 {
+                class: "text-4xl font-bold text-gray-900 dark:text-white mb-8",
+                "所有分类"
+            }
+            div {
+                class: "grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-4",
+                for (category, count) in category_counts.iter() {
+                    Link {
+                        key: "{category}",
+                        to: Route::BlogCategory { category: category.to_string() },
+                        class: "flex justify-between items-center bg-white dark:bg-gray-800 rounded-lg shadow-md hover:shadow-lg transition-shadow p-4",
+                        span { class: "text-lg font-semibold text-gray-900 dark:text-white", "{category}" }
+                        span { class: "text-sm text-gray-500 dark:text-gray-500", "{count} 篇" }
+                    }
+                }
+            }
+            if category_counts.is_empty() {
+                div {
+                    class: "text-center py-12 text-gray-500 dark:text-gray-500",
+                    p { "暂无分类" }
+                }
+            }
+        }
+    }
+}
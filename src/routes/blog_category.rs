@@ -1,20 +1,63 @@
+use crate::components::content::{Pagination, PostCard};
+use crate::routes::{NotFound, Route};
+use crate::utils::AppState;
 use dioxus::prelude::*;
 
-/// 分类页组件
+/// 分类页组件（第一页）
 #[component]
 pub fn BlogCategory(category: String) -> Element {
+    rsx! {
+        BlogCategoryPage { category, page: 1 }
+    }
+}
+
+/// 分类分页组件：列出该分类下的文章（从新到旧）
+#[component]
+pub fn BlogCategoryPage(category: String, page: usize) -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+    let state = app_state.read();
+    let current = state.post_index.get_category_page(&category, page, state.site.posts_per_page);
+
+    if !current.is_valid() {
+        return rsx! { NotFound {} };
+    }
+
+    let pages: Vec<Route> = (1..=current.total_pages)
+        .map(|n| Route::category_page(&category, n))
+        .collect();
+
     rsx! {
         div {
             class: "container mx-auto px-4 py-8",
-            h1 {
-                class: "text-4xl font-bold mb-8",
-                "分类: {category}"
+            div {
+                class: "mb-8",
+                h1 {
+                    class: "text-4xl font-bold text-gray-900 dark:text-white mb-4",
+                    "分类: {category}"
+                }
+                p {
+                    class: "text-gray-600 dark:text-gray-400",
+                    {format!("共 {} 篇文章", current.total_posts)}
+                }
+                Link {
+                    to: Route::BlogCategories {},
+                    class: "text-sm text-blue-600 dark:text-blue-400 hover:underline",
+                    "← 所有分类"
+                }
             }
             div {
-                class: "text-center py-12",
-                p { "分类筛选功能即将实现..." }
+                class: "space-y-6",
+                for post in current.posts.iter() {
+                    PostCard { key: "{post.file_path}", post: (*post).clone() }
+                }
+            }
+            if current.posts.is_empty() {
+                div {
+                    class: "text-center py-12 text-gray-500 dark:text-gray-500",
+                    p { "该分类下暂无文章" }
+                }
             }
+            Pagination { current: page, pages }
         }
     }
 }
-
@@ -1,14 +1,29 @@
-use crate::content::load_embedded_blogs;
-use crate::routes::Route;
+use crate::components::content::{Pagination, PostCard};
+use crate::routes::{NotFound, Route};
+use crate::utils::AppState;
 use dioxus::prelude::*;
 
-/// 博客列表页组件
+/// 博客列表页组件（第一页）
 #[component]
 pub fn BlogList() -> Element {
-    // 加载嵌入的博客文章
-    let post_index = use_signal(|| load_embedded_blogs().unwrap_or_default());
-    let posts = post_index.read();
-    let all_posts = posts.get_all_posts();
+    rsx! {
+        BlogListPage { page: 1 }
+    }
+}
+
+/// 博客列表分页组件
+#[component]
+pub fn BlogListPage(page: usize) -> Element {
+    // 从全局状态读取文章索引
+    let app_state = use_context::<Signal<AppState>>();
+    let state = app_state.read();
+    let current = state.post_index.get_page(page, state.site.posts_per_page);
+
+    if !current.is_valid() {
+        return rsx! { NotFound {} };
+    }
+
+    let pages: Vec<Route> = (1..=current.total_pages).map(Route::blog_page).collect();
 
     rsx! {
         div { class: "container mx-auto px-4 py-8",
@@ -18,61 +33,36 @@ pub fn BlogList() -> Element {
                     "所有文章"
                 }
                 p { class: "text-gray-600 dark:text-gray-400",
-                    {format!("共找到 {} 篇文章", all_posts.len())}
+                    {format!("共找到 {} 篇文章", current.total_posts)}
+                }
+                div { class: "flex gap-4 mt-2 text-sm",
+                    Link {
+                        to: Route::BlogTags {},
+                        class: "text-blue-600 dark:text-blue-400 hover:underline",
+                        "按标签浏览"
+                    }
+                    Link {
+                        to: Route::BlogCategories {},
+                        class: "text-blue-600 dark:text-blue-400 hover:underline",
+                        "按分类浏览"
+                    }
                 }
             }
 
             // 文章列表
             div { class: "space-y-6",
-                for post in all_posts.iter() {
-                    div { class: "bg-white dark:bg-gray-800 rounded-lg shadow-md hover:shadow-lg transition-shadow p-6",
-                        div { class: "flex flex-col md:flex-row gap-4",
-                            // 封面图片占位
-                            // div {
-                            //     class: "w-full md:w-48 h-32 bg-gray-200 dark:bg-gray-700 rounded flex-shrink-0",
-                            // }
-                            // 文章信息
-                            div { class: "flex-1",
-                                h2 { class: "text-2xl font-semibold text-gray-900 dark:text-white mb-2",
-                                    Link {
-                                        to: Route::BlogPost {
-                                            slug: post.metadata.slug.clone().unwrap_or_else(|| "unknown".to_string()),
-                                        },
-                                        class: "hover:text-blue-600 dark:hover:text-blue-400 transition-colors",
-                                        "{post.metadata.title}"
-                                    }
-                                }
-                                div {
-                                    class: "flex items-center gap-4 mb-2 text-sm text-gray-500 dark:text-gray-500",
-                                    if let Some(date) = post.metadata.date {
-                                        span { "{date}" }
-                                        span { "•" }
-                                    }
-                                                                // span { "5 分钟阅读" }
-                                }
-                                p { class: "text-gray-600 dark:text-gray-400 mb-4",
-                                    "{post.metadata.summary.clone().unwrap_or_default()}"
-                                }
-                                div { class: "flex flex-wrap gap-2",
-                                    if let Some(tags) = &post.metadata.tags {
-                                        for tag in tags {
-                                            span { class: "px-2 py-1 bg-gray-100 dark:bg-gray-700 text-gray-700 dark:text-gray-300 rounded text-sm",
-                                                "{tag}"
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+                for post in current.posts.iter() {
+                    PostCard { key: "{post.file_path}", post: (*post).clone() }
                 }
             }
 
-            if all_posts.is_empty() {
+            if current.posts.is_empty() {
                 div { class: "text-center mt-12 text-gray-500 dark:text-gray-500",
                     p { "暂无文章" }
                 }
             }
+
+            Pagination { current: page, pages }
         }
     }
 }
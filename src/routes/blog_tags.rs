@@ -0,0 +1,71 @@
+use crate::content::tag_cloud_weight;
+use crate::routes::Route;
+use crate::utils::AppState;
+use dioxus::prelude::*;
+
+/// 标签云字号，按权重从小到大
+const TAG_CLOUD_SIZES: [&str; 5] = ["text-sm", "text-base", "text-lg", "text-xl", "text-2xl"];
+
+/// 标签总览页组件：按文章数加权的标签云
+#[component]
+pub fn BlogTags() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+    let state = app_state.read();
+    let tag_counts = state.post_index.tag_counts();
+
+    let min = tag_counts.iter().map(|(_, count)| *count).min().unwrap_or(0);
+    let max = tag_counts.iter().map(|(_, count)| *count).max().unwrap_or(0);
+
+    // 标签云按名称排列，字号表示文章数
+    let mut cloud: Vec<(String, usize, &str)> = tag_counts
+        .iter()
+        .map(|(tag, count)| {
+            let weight = tag_cloud_weight(*count, min, max, TAG_CLOUD_SIZES.len());
+            (tag.to_string(), *count, TAG_CLOUD_SIZES[weight - 1])
+        })
+        .collect();
+    cloud.sort_by(|a, b| a.0.cmp(&b.0));
+
+    rsx! {
+        div {
+            class: "container mx-auto px-4 py-8",
+            h1 {
+                class: "text-4xl font-bold text-gray-900 dark:text-white mb-8",
+                "所有标签"
+            }
+            div {
+                class: "flex flex-wrap items-baseline gap-x-4 gap-y-3 mb-12",
+                for (tag, count, size) in cloud {
+                    Link {
+                        key: "{tag}",
+                        to: Route::BlogTag { tag: tag.clone() },
+                        class: "{size} text-blue-600 dark:text-blue-400 hover:underline",
+                        title: "{count} 篇文章",
+                        "{tag}"
+                    }
+                }
+            }
+            ul {
+                class: "divide-y divide-gray-200 dark:divide-gray-700",
+                for (tag, count) in tag_counts.iter() {
+                    li {
+                        key: "{tag}",
+                        class: "flex justify-between py-2",
+                        Link {
+                            to: Route::BlogTag { tag: tag.to_string() },
+                            class: "text-gray-900 dark:text-white hover:text-blue-600 dark:hover:text-blue-400",
+                            "{tag}"
+                        }
+                        span { class: "text-gray-500 dark:text-gray-500", "{count}" }
+                    }
+                }
+            }
+            if tag_counts.is_empty() {
+                div {
+                    class: "text-center py-12 text-gray-500 dark:text-gray-500",
+                    p { "暂无标签" }
+                }
+            }
+        }
+    }
+}
@@ -1,14 +1,14 @@
 use dioxus::prelude::*;
 use crate::routes::Route;
-use crate::content::load_embedded_blogs;
+use crate::utils::AppState;
 
 /// 首页组件
 #[component]
 pub fn Home() -> Element {
-    // 加载嵌入的博客文章
-    let post_index = use_signal(|| load_embedded_blogs().unwrap_or_default());
-    let posts_read = post_index.read();
-    let recent_posts = posts_read.get_recent_posts(6);
+    // 从全局状态读取文章索引
+    let app_state = use_context::<Signal<AppState>>();
+    let state = app_state.read();
+    let recent_posts = state.get_recent_posts(6);
     
     rsx! {
         div {
@@ -40,9 +40,9 @@ pub fn Home() -> Element {
                             class: "text-xl font-semibold text-gray-900 dark:text-white mb-2",
                             "{post.metadata.title}"
                         }
-                        p {
+                        div {
                             class: "text-gray-600 dark:text-gray-400 text-sm mb-4",
-                            "{post.metadata.summary.clone().unwrap_or_default()}"
+                            dangerous_inner_html: "{post.excerpt()}",
                         }
                         div {
                             class: "flex items-center justify-between",
@@ -1,18 +1,26 @@
 use dioxus::prelude::*;
 use crate::components::layout::MainLayout;
+use crate::content::{PageMeta, PostIndex, SiteConfig};
+use blog_content::site::{blog_path, category_path, post_path, tag_path};
 
 mod home;
 mod blog_list;
 mod blog_post;
 mod blog_tag;
+mod blog_tags;
 mod blog_category;
+mod blog_categories;
+mod search;
 mod not_found;
 
 pub use home::Home;
-pub use blog_list::BlogList;
+pub use blog_list::{BlogList, BlogListPage};
 pub use blog_post::BlogPost;
-pub use blog_tag::BlogTag;
-pub use blog_category::BlogCategory;
+pub use blog_tag::{BlogTag, BlogTagPage};
+pub use blog_tags::BlogTags;
+pub use blog_category::{BlogCategory, BlogCategoryPage};
+pub use blog_categories::BlogCategories;
+pub use search::Search;
 pub use not_found::NotFound;
 
 /// 路由枚举定义
@@ -23,13 +31,101 @@ pub enum Route {
     Home {},
     #[route("/blog")]
     BlogList {},
+    #[route("/blog/page/:page")]
+    BlogListPage { page: usize },
+    #[route("/blog/tags")]
+    BlogTags {},
+    #[route("/blog/categories")]
+    BlogCategories {},
     #[route("/blog/:slug")]
     BlogPost { slug: String },
     #[route("/blog/tag/:tag")]
     BlogTag { tag: String },
+    #[route("/blog/tag/:tag/page/:page")]
+    BlogTagPage { tag: String, page: usize },
     #[route("/blog/category/:category")]
     BlogCategory { category: String },
+    #[route("/blog/category/:category/page/:page")]
+    BlogCategoryPage { category: String, page: usize },
+    #[route("/search?:q")]
+    Search { q: String },
     #[route("/404")]
     NotFound {},
 }
 
+
+impl Route {
+    /// 博客列表第 `page` 页，第一页使用不带页码的地址
+    pub fn blog_page(page: usize) -> Self {
+        if page <= 1 {
+            Route::BlogList {}
+        } else {
+            Route::BlogListPage { page }
+        }
+    }
+
+    /// 标签列表第 `page` 页，第一页使用不带页码的地址
+    pub fn tag_page(tag: &str, page: usize) -> Self {
+        if page <= 1 {
+            Route::BlogTag { tag: tag.to_string() }
+        } else {
+            Route::BlogTagPage { tag: tag.to_string(), page }
+        }
+    }
+
+    /// 分类列表第 `page` 页，第一页使用不带页码的地址
+    pub fn category_page(category: &str, page: usize) -> Self {
+        if page <= 1 {
+            Route::BlogCategory { category: category.to_string() }
+        } else {
+            Route::BlogCategoryPage { category: category.to_string(), page }
+        }
+    }
+
+    /// 当前页面的 SEO 元数据；找不到的文章按 404 页面处理
+    pub fn page_meta(&self, index: &PostIndex, site: &SiteConfig) -> PageMeta {
+        let paged = |title: String, page: usize| {
+            if page <= 1 {
+                title
+            } else {
+                format!("{} - 第 {} 页", title, page)
+            }
+        };
+        match self {
+            Route::Home {} => PageMeta::page(site, None, "/"),
+            Route::BlogList {} => PageMeta::page(site, Some("博客"), &blog_path(1)),
+            Route::BlogListPage { page } => {
+                PageMeta::page(site, Some(&paged("博客".to_string(), *page)), &blog_path(*page))
+            }
+            Route::BlogTags {} => PageMeta::page(site, Some("标签"), "/blog/tags"),
+            Route::BlogCategories {} => PageMeta::page(site, Some("分类"), "/blog/categories"),
+            Route::BlogPost { slug } => match index.get_post_by_slug(slug) {
+                Some(post) => PageMeta::post(site, post),
+                None => PageMeta::page(site, Some("页面未找到"), &post_path(slug)),
+            },
+            Route::BlogTag { tag } => {
+                PageMeta::page(site, Some(&format!("标签: {}", tag)), &tag_path(tag, 1))
+            }
+            Route::BlogTagPage { tag, page } => PageMeta::page(
+                site,
+                Some(&paged(format!("标签: {}", tag), *page)),
+                &tag_path(tag, *page),
+            ),
+            Route::BlogCategory { category } => PageMeta::page(
+                site,
+                Some(&format!("分类: {}", category)),
+                &category_path(category, 1),
+            ),
+            Route::BlogCategoryPage { category, page } => PageMeta::page(
+                site,
+                Some(&paged(format!("分类: {}", category), *page)),
+                &category_path(category, *page),
+            ),
+            Route::Search { q } if q.trim().is_empty() => PageMeta::page(site, Some("搜索"), "/search"),
+            Route::Search { q } => {
+                PageMeta::page(site, Some(&format!("搜索: {}", q.trim())), "/search")
+            }
+            Route::NotFound {} => PageMeta::page(site, Some("页面未找到"), "/404"),
+        }
+    }
+}
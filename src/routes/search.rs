@@ -0,0 +1,79 @@
+use blog_content::search::{highlight, snippet, TextPart};
+use crate::content::{post_html, search_index};
+use crate::routes::Route;
+use dioxus::prelude::*;
+
+/// 搜索结果摘录的长度（字符数）
+const SNIPPET_LENGTH: usize = 120;
+
+/// 搜索结果页：`/search?q=关键词`，在本地检索构建时生成的索引
+#[component]
+pub fn Search(q: String) -> Element {
+    let query = q.trim().to_string();
+    let hits = search_index().search(&query);
+
+    rsx! {
+        div {
+            class: "container mx-auto px-4 py-8 max-w-4xl",
+            h1 {
+                class: "text-4xl font-bold text-gray-900 dark:text-white mb-4",
+                "搜索"
+            }
+            if query.is_empty() {
+                p {
+                    class: "text-gray-600 dark:text-gray-400",
+                    "在页面顶部的搜索框中输入关键词"
+                }
+            } else {
+                p {
+                    class: "text-gray-600 dark:text-gray-400 mb-8",
+                    {format!("“{}” 共找到 {} 篇文章", query, hits.len())}
+                }
+                div {
+                    class: "space-y-6",
+                    for hit in hits.iter() {
+                        div {
+                            key: "{hit.doc.slug}",
+                            class: "bg-white dark:bg-gray-800 rounded-lg shadow-md p-6",
+                            h2 {
+                                class: "text-2xl font-semibold text-gray-900 dark:text-white mb-2",
+                                Link {
+                                    to: Route::BlogPost { slug: hit.doc.slug.clone() },
+                                    class: "hover:text-blue-600 dark:hover:text-blue-400 transition-colors",
+                                    Highlighted { parts: highlight(&hit.doc.title, &query) }
+                                }
+                            }
+                            if let Some(date) = &hit.doc.date {
+                                div { class: "text-sm text-gray-500 dark:text-gray-500 mb-2", "{date}" }
+                            }
+                            p {
+                                class: "text-gray-600 dark:text-gray-400",
+                                Highlighted { parts: snippet(&hit.doc.text(post_html(&hit.doc.slug).unwrap_or_default()), &query, SNIPPET_LENGTH) }
+                            }
+                        }
+                    }
+                }
+                if hits.is_empty() {
+                    div {
+                        class: "text-center py-12 text-gray-500 dark:text-gray-500",
+                        p { "没有找到相关文章，换个关键词试试" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 渲染高亮片段，命中的关键词用 `<mark>` 标出
+#[component]
+fn Highlighted(parts: Vec<TextPart>) -> Element {
+    rsx! {
+        for part in parts.iter() {
+            if part.highlight {
+                mark { class: "bg-yellow-200 dark:bg-yellow-700 dark:text-white rounded-sm", "{part.text}" }
+            } else {
+                "{part.text}"
+            }
+        }
+    }
+}
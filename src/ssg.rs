@@ -0,0 +1,144 @@
+//! 静态站点生成
+//!
+//! 用 Dioxus SSR 把每个路由（包括每篇文章、每个标签和分类及其分页）渲染成
+//! `<输出目录>/<路径>/index.html`。页面外壳取自 `dx bundle` 生成的 `index.html`，
+//! 其中已经引用了 WASM 包。页面不带 hydration 数据，浏览器加载 WASM 后客户端先清空
+//! 挂载点再重新渲染（见 `main.rs` 的 `clear_prerendered_content`）。
+//!
+//! 用法：先 `dx bundle --platform web`，再
+//! `cargo run --no-default-features --features ssg -- <dx 输出的 public 目录>`
+
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+use anyhow::{Context, Result};
+use blog_content::prerender::{
+    asset_head_html, inject_head, inject_into_shell, output_file, prerender_paths, FALLBACK_SHELL,
+};
+use dioxus::prelude::*;
+use dioxus_history::{provide_history_context, MemoryHistory};
+
+use crate::content::{load_post_index, load_site_config, PostIndex, SiteConfig};
+use crate::routes::Route;
+use crate::utils::AppState;
+use crate::{CSS, TAILWIND_CSS, THEME_JS};
+
+/// 默认输出目录
+const DEFAULT_OUT_DIR: &str = "dist";
+
+/// 以指定路径为初始地址的应用，只用于服务端渲染
+#[component]
+fn StaticApp(path: String) -> Element {
+    let app_state = use_signal(AppState::new);
+    use_context_provider(|| app_state);
+    use_hook(|| provide_history_context(Rc::new(MemoryHistory::with_initial_path(path))));
+
+    rsx! {
+        Router::<Route> {}
+    }
+}
+
+/// 渲染单个路径，输出不带 hydration 数据的静态 HTML 片段
+fn render_path(path: &str) -> String {
+    let mut dom = VirtualDom::new_with_props(
+        StaticApp,
+        StaticAppProps {
+            path: path.to_string(),
+        },
+    );
+    dom.rebuild_in_place();
+    dioxus_ssr::render(&dom)
+}
+
+/// `App` 中 `document::Stylesheet` 和 `document::Script` 引用的资源，使用 `asset!()` 解析后的地址
+fn asset_head() -> String {
+    asset_head_html(
+        &[TAILWIND_CSS.to_string(), CSS.to_string()],
+        &[THEME_JS.to_string()],
+    )
+}
+
+/// 把单个路径渲染为完整页面
+fn render_page(shell: &str, path: &str, index: &PostIndex, site: &SiteConfig) -> Result<String> {
+    // SSR 不会输出 document:: 头部元素，资源引用和页面元数据直接写入外壳的 <head>
+    let route: Route = path
+        .parse()
+        .map_err(|e| anyhow::anyhow!("无法解析路由 {}: {}", path, e))?;
+    let head = format!("{}{}", asset_head(), route.page_meta(index, site).to_head_html());
+    inject_into_shell(shell, &render_path(path)).and_then(|html| inject_head(&html, &head))
+}
+
+/// 生成所有静态页面，返回生成的页面数
+pub fn generate(out_dir: &Path) -> Result<usize> {
+    let shell_path = out_dir.join("index.html");
+    let shell = match fs::read_to_string(&shell_path) {
+        Ok(shell) => shell,
+        Err(_) => {
+            eprintln!(
+                "警告：{} 不存在，使用不含 WASM 的最简页面外壳（请先运行 dx bundle）",
+                shell_path.display()
+            );
+            FALLBACK_SHELL.to_string()
+        }
+    };
+
+    let index = load_post_index();
+    let site = load_site_config();
+    let paths = prerender_paths(&index, &site);
+    for path in &paths {
+        let html = render_page(&shell, path, &index, &site)
+            .with_context(|| format!("{} 不是 dx 生成的原始页面外壳", shell_path.display()))?;
+        let file = out_dir.join(output_file(path)?);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("无法创建目录 {}", parent.display()))?;
+        }
+        fs::write(&file, &html).with_context(|| format!("无法写入 {}", file.display()))?;
+
+        // 静态托管平台通常以根目录下的 404.html 作为未找到页面
+        if path == "/404" {
+            fs::write(out_dir.join("404.html"), &html)
+                .with_context(|| format!("无法写入 {}", out_dir.join("404.html").display()))?;
+        }
+    }
+
+    Ok(paths.len())
+}
+
+/// 命令行入口：第一个参数为输出目录，缺省为 `dist`
+pub fn run() -> Result<()> {
+    let out_dir = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_OUT_DIR.to_string());
+    let count = generate(Path::new(&out_dir))?;
+    println!("已生成 {} 个静态页面到 {}", count, out_dir);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_page_into_shell() {
+        let index = load_post_index();
+        let site = load_site_config();
+        let post = &index.posts[0];
+        let path = blog_content::site::post_path(&post.slug());
+        let html = render_page(FALLBACK_SHELL, &path, &index, &site).unwrap();
+
+        // 正文进入挂载点，且只出现一次
+        let main = html.split(r#"<div id="main">"#).nth(1).unwrap();
+        assert!(main.contains(&post.metadata.title));
+        assert_eq!(html.matches(r#"<div id="main">"#).count(), 1);
+        assert!(!html.contains("data-node-hydration"));
+
+        // 资源引用和页面元数据写入 <head>
+        let head = html.split("</head>").next().unwrap();
+        for asset in [TAILWIND_CSS.to_string(), CSS.to_string(), THEME_JS.to_string()] {
+            assert!(head.contains(&format!(r#""{}""#, asset)), "缺少 {}", asset);
+        }
+        assert!(head.contains("<title>"));
+    }
+}
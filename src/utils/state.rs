@@ -1,4 +1,4 @@
-use crate::content::{PostIndex, Post};
+use crate::content::{load_post_index, load_site_config, PostIndex, Post, SiteConfig};
 use dioxus::prelude::*;
 
 /// 应用全局状态
@@ -6,15 +6,18 @@ use dioxus::prelude::*;
 pub struct AppState {
     /// 文章索引
     pub post_index: PostIndex,
+    /// 站点配置
+    pub site: SiteConfig,
     /// 主题：light 或 dark
     pub theme: Signal<String>,
 }
 
 impl AppState {
-    /// 创建新的 AppState
+    /// 创建新的 AppState，文章索引来自构建时生成的 `posts_index.json`
     pub fn new() -> Self {
         Self {
-            post_index: PostIndex::new(),
+            post_index: load_post_index(),
+            site: load_site_config(),
             theme: Signal::new("light".to_string()),
         }
     }